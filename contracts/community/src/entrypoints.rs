@@ -46,7 +46,8 @@ pub fn execute(
         ExecuteMsg::Transfer {
             recipient,
             amount,
-        } => executions::transfer(deps, env, info, recipient, amount),
+            msg,
+        } => executions::transfer(deps, env, info, recipient, amount, msg),
     }
 }
 
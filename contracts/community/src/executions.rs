@@ -1,4 +1,4 @@
-use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, StdError, Uint128};
+use cosmwasm_std::{Binary, DepsMut, Env, MessageInfo, Response, StdError, Uint128};
 use cw20::Cw20ExecuteMsg;
 
 use terrapoker::common::ContractResult;
@@ -175,6 +175,7 @@ pub fn transfer(
     info: MessageInfo,
     recipient: String,
     amount: Uint128,
+    msg: Option<Binary>,
 ) -> ContractResult<Response> {
     // Validate
     let config = ContractConfig::load(deps.storage)?;
@@ -215,12 +216,22 @@ pub fn transfer(
         }
     };
 
-    response = response.add_message(message_factories::wasm_execute(
-        &config.managing_token,
-        &Cw20ExecuteMsg::Transfer {
-            recipient: deps.api.addr_validate(&recipient)?.to_string(),
+    let recipient = deps.api.addr_validate(&recipient)?.to_string();
+    let transfer_msg = match msg {
+        Some(msg) => Cw20ExecuteMsg::Send {
+            contract: recipient.clone(),
+            amount,
+            msg,
+        },
+        None => Cw20ExecuteMsg::Transfer {
+            recipient: recipient.clone(),
             amount,
         },
+    };
+
+    response = response.add_message(message_factories::wasm_execute(
+        &config.managing_token,
+        &transfer_msg,
     ));
 
     response = response.add_attribute("requester", info.sender.as_str());
@@ -19,7 +19,7 @@ pub fn exec(
     recipient: String,
     amount: Uint128,
 ) -> ContractResult<Response> {
-    transfer(deps.as_mut(), env, info, recipient, amount)
+    transfer(deps.as_mut(), env, info, recipient, amount, None)
 }
 
 pub fn will_success(
@@ -104,6 +104,38 @@ fn succeed_governance() {
     ]);
 }
 
+#[test]
+fn succeed_with_msg() {
+    let mut deps = custom_deps();
+    deps.querier.with_token_balances(&[(
+        MANAGING_TOKEN,
+        &[(COMMUNITY, &Uint128::new(100))],
+    )]);
+
+    super::instantiate::default(&mut deps);
+
+    let msg = to_binary("hook").unwrap();
+    let response = transfer(
+        deps.as_mut(),
+        community_env(),
+        governance_sender(),
+        DEFAULT_SENDER.to_string(),
+        Uint128::new(100),
+        Some(msg.clone()),
+    ).unwrap();
+    assert_eq!(response.messages, vec![
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MANAGING_TOKEN.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: DEFAULT_SENDER.to_string(),
+                amount: Uint128::new(100),
+                msg,
+            }).unwrap(),
+            funds: vec![],
+        })),
+    ]);
+}
+
 #[test]
 fn failed_invalid_permission() {
     let mut deps = custom_deps();
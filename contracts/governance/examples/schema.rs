@@ -25,16 +25,28 @@ fn main() {
     export_schema(&schema_for!(MigrateMsg), &out_dir);
 
     export_schema(&schema_for!(VoteInfoMsg), &out_dir);
+    export_schema(&schema_for!(DepositInfoMsg), &out_dir);
 
     export_schema(&schema_for!(QueryMsg), &out_dir);
     export_schema(&schema_for!(ContractConfigResponse), &out_dir);
     export_schema(&schema_for!(StakingStateResponse), &out_dir);
     export_schema(&schema_for!(StakerStateResponse), &out_dir);
+    export_schema(&schema_for!(StakerVotesResponse), &out_dir);
+    export_schema(&schema_for!(UnstakeRequestsResponse), &out_dir);
+    export_schema(&schema_for!(SimulateUnstakeAmountResponse), &out_dir);
+    export_schema(&schema_for!(ExchangeRateResponse), &out_dir);
+    export_schema(&schema_for!(ExchangeRateHistoryResponse), &out_dir);
+    export_schema(&schema_for!(StakingAprResponse), &out_dir);
     export_schema(&schema_for!(PollConfigResponse), &out_dir);
     export_schema(&schema_for!(PollStateResponse), &out_dir);
     export_schema(&schema_for!(PollResponse), &out_dir);
     export_schema(&schema_for!(PollsResponse), &out_dir);
     export_schema(&schema_for!(PollCountResponse), &out_dir);
+    export_schema(&schema_for!(PollDepositsResponse), &out_dir);
+    export_schema(&schema_for!(PollExecutionResultsResponse), &out_dir);
+    export_schema(&schema_for!(PollTallyResponse), &out_dir);
     export_schema(&schema_for!(VotersResponse), &out_dir);
     export_schema(&schema_for!(VotingPowerResponse), &out_dir);
+    export_schema(&schema_for!(DelegationResponse), &out_dir);
+    export_schema(&schema_for!(DelegatedPowerResponse), &out_dir);
 }
\ No newline at end of file
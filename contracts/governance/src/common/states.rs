@@ -88,7 +88,10 @@ pub fn load_contract_staking_token_balance(deps: Deps) -> StdResult<Uint128> {
         &contract_config.address,
     )?;
 
-    let total_locked = StakingState::load(deps.storage)?.total_unstake_locked;
+    let staking_state = StakingState::load(deps.storage)?;
+    let total_locked = staking_state.total_unstake_locked
+        + staking_state.total_voting_reward
+        + staking_state.unallocated_voting_reward;
 
     Ok(contract_balance.checked_sub(total_locked)?)
 }
\ No newline at end of file
@@ -0,0 +1,76 @@
+use cosmwasm_std::{DepsMut, Env, MessageInfo, Response, StdError};
+
+use terrapoker::common::ContractResult;
+use terrapoker::errors::ContractError;
+use terrapoker::utils::make_response;
+
+use crate::common::states::load_gov_token_balance;
+
+use super::states::{load_delegators, MAX_DELEGATORS, remove_delegation, save_delegation};
+
+pub fn delegate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    delegate: String,
+) -> ContractResult<Response> {
+    // Validate
+    let delegate = deps.api.addr_validate(&delegate)?;
+
+    if delegate == info.sender {
+        return Err(ContractError::Std(StdError::generic_err("Can not delegate to yourself")));
+    }
+
+    if load_gov_token_balance(deps.as_ref(), &info.sender, None)?.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err("Insufficient balance to delegate")));
+    }
+
+    let delegators = load_delegators(deps.storage, &delegate, None)?;
+    let is_delegator = delegators.contains(&info.sender);
+
+    // Execute
+    let mut response = make_response("delegate");
+
+    if !is_delegator && delegators.len() >= MAX_DELEGATORS {
+        // delegators without xTPT add no power, so they must not hold a slot
+        for delegator in delegators.iter() {
+            if load_gov_token_balance(deps.as_ref(), delegator, None)?.is_zero() {
+                remove_delegation(deps.storage, delegator, env.block.height)?;
+                response = response.add_attribute("pruned_delegator", delegator.as_str());
+            }
+        }
+
+        if load_delegators(deps.storage, &delegate, None)?.len() >= MAX_DELEGATORS {
+            return Err(ContractError::ExceedLimit {});
+        }
+    }
+
+    let prev_delegate = remove_delegation(deps.storage, &info.sender, env.block.height)?;
+    save_delegation(deps.storage, &info.sender, &delegate, env.block.height)?;
+
+    response = response.add_attribute("delegator", info.sender.as_str());
+    response = response.add_attribute("delegate", delegate.as_str());
+
+    if let Some(prev_delegate) = prev_delegate {
+        response = response.add_attribute("prev_delegate", prev_delegate.as_str());
+    }
+
+    Ok(response)
+}
+
+pub fn undelegate(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> ContractResult<Response> {
+    // Execute
+    let mut response = make_response("undelegate");
+
+    let prev_delegate = remove_delegation(deps.storage, &info.sender, env.block.height)?
+        .ok_or(ContractError::Std(StdError::generic_err("Not delegated")))?;
+
+    response = response.add_attribute("delegator", info.sender.as_str());
+    response = response.add_attribute("prev_delegate", prev_delegate.as_str());
+
+    Ok(response)
+}
@@ -0,0 +1,6 @@
+pub mod states;
+pub mod executions;
+pub mod queries;
+
+#[cfg(test)]
+pub mod tests;
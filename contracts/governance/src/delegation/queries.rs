@@ -0,0 +1,38 @@
+use cosmwasm_std::{Deps, Env};
+
+use terrapoker::common::ContractResult;
+use terrapoker::governance::query_msgs::{DelegatedPowerResponse, DelegationResponse};
+
+use super::states::{load_delegate, load_delegated_power, load_delegators};
+
+pub fn get_delegation(
+    deps: Deps,
+    _env: Env,
+    address: String,
+) -> ContractResult<DelegationResponse> {
+    let address = deps.api.addr_validate(&address)?;
+
+    Ok(
+        DelegationResponse {
+            delegate: load_delegate(deps.storage, &address, None)?.map(|d| d.to_string()),
+        }
+    )
+}
+
+pub fn get_delegated_power(
+    deps: Deps,
+    _env: Env,
+    address: String,
+    height: Option<u64>,
+) -> ContractResult<DelegatedPowerResponse> {
+    let address = deps.api.addr_validate(&address)?;
+
+    Ok(
+        DelegatedPowerResponse {
+            delegators: load_delegators(deps.storage, &address, height)?.iter()
+                .map(|d| d.to_string())
+                .collect(),
+            delegated_power: load_delegated_power(deps, &address, height, None)?,
+        }
+    )
+}
@@ -0,0 +1,86 @@
+use cosmwasm_std::{Addr, Deps, StdResult, Storage, Uint128};
+use cw_storage_plus::{SnapshotMap, Strategy};
+
+use crate::common::states::load_gov_token_balance;
+use crate::poll::states::Poll;
+
+pub const MAX_DELEGATORS: usize = 100;
+
+// maps delegator to delegate
+const DELEGATIONS: SnapshotMap<&Addr, Addr> = SnapshotMap::new(
+    "delegation",
+    "delegation__checkpoints",
+    "delegation__changelog",
+    Strategy::EveryBlock,
+);
+
+// maps delegate to delegators
+const DELEGATORS: SnapshotMap<&Addr, Vec<Addr>> = SnapshotMap::new(
+    "delegator",
+    "delegator__checkpoints",
+    "delegator__changelog",
+    Strategy::EveryBlock,
+);
+
+pub fn load_delegate(storage: &dyn Storage, delegator: &Addr, height: Option<u64>) -> StdResult<Option<Addr>> {
+    if let Some(height) = height {
+        DELEGATIONS.may_load_at_height(storage, delegator, height)
+    } else {
+        DELEGATIONS.may_load(storage, delegator)
+    }
+}
+
+pub fn load_delegators(storage: &dyn Storage, delegate: &Addr, height: Option<u64>) -> StdResult<Vec<Addr>> {
+    let delegators = if let Some(height) = height {
+        DELEGATORS.may_load_at_height(storage, delegate, height)?
+    } else {
+        DELEGATORS.may_load(storage, delegate)?
+    };
+
+    Ok(delegators.unwrap_or_default())
+}
+
+pub fn save_delegation(storage: &mut dyn Storage, delegator: &Addr, delegate: &Addr, height: u64) -> StdResult<()> {
+    let mut delegators = load_delegators(storage, delegate, None)?;
+    delegators.push(delegator.clone());
+
+    DELEGATORS.save(storage, delegate, &delegators, height)?;
+    DELEGATIONS.save(storage, delegator, delegate, height)
+}
+
+pub fn remove_delegation(storage: &mut dyn Storage, delegator: &Addr, height: u64) -> StdResult<Option<Addr>> {
+    let delegate = load_delegate(storage, delegator, None)?;
+
+    if let Some(delegate) = delegate.as_ref() {
+        let mut delegators = load_delegators(storage, delegate, None)?;
+        delegators.retain(|d| d != delegator);
+
+        if delegators.is_empty() {
+            DELEGATORS.remove(storage, delegate, height)?;
+        } else {
+            DELEGATORS.save(storage, delegate, &delegators, height)?;
+        }
+
+        DELEGATIONS.remove(storage, delegator, height)?;
+    }
+
+    Ok(delegate)
+}
+
+// returns the sum of delegators' balances at the height.
+// delegators who voted directly on the poll are excluded.
+pub fn load_delegated_power(deps: Deps, delegate: &Addr, height: Option<u64>, poll: Option<&Poll>) -> StdResult<Uint128> {
+    let mut delegated_power = Uint128::zero();
+
+    for delegator in load_delegators(deps.storage, delegate, height)?.iter() {
+        if let Some(poll) = poll {
+            if poll.may_load_voter(deps.storage, delegator)?.is_some() {
+                continue;
+            }
+        }
+
+        delegated_power += load_gov_token_balance(deps, delegator, height)?;
+    }
+
+    Ok(delegated_power)
+}
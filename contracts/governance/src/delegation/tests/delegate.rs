@@ -0,0 +1,230 @@
+use cosmwasm_std::{Addr, attr, Env, MessageInfo, Response, Uint128};
+use cosmwasm_std::testing::mock_info;
+
+use terrapoker::common::ContractResult;
+use terrapoker::errors::ContractError;
+use terrapoker::governance::enumerations::VoteOption;
+use terrapoker::mock_querier::{custom_deps, CustomDeps};
+use terrapoker::test_constants::governance::*;
+use terrapoker::test_utils::expect_generic_err;
+
+use crate::delegation::executions::delegate;
+use crate::delegation::states::{load_delegate, load_delegators};
+use crate::poll::states::Poll;
+use crate::poll::tests::cast_vote::{VOTER1, VOTER2, VOTER3};
+use crate::staking::states::StakerState;
+use crate::tests::init_default;
+
+pub fn exec(
+    deps: &mut CustomDeps,
+    env: Env,
+    info: MessageInfo,
+    delegate_addr: &str,
+) -> ContractResult<Response> {
+    delegate(deps.as_mut(), env, info, delegate_addr.to_string())
+}
+
+pub fn with_balance(deps: &mut CustomDeps, address: &str, amount: Uint128) {
+    deps.querier.plus_token_balances(&[(
+        GOVERNANCE_TOKEN,
+        &[(address, &amount)],
+    )]);
+}
+
+// delegates before the snapshot height of polls created at governance_env()
+pub fn will_success(
+    deps: &mut CustomDeps,
+    delegator: &str,
+    delegate_addr: &str,
+) -> (Env, MessageInfo, Response) {
+    let env = governance_env_height(governance_env().block.height - 10);
+    let info = mock_info(delegator, &[]);
+
+    let response = exec(deps, env.clone(), info.clone(), delegate_addr).unwrap();
+
+    (env, info, response)
+}
+
+#[test]
+fn succeed() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+    with_balance(&mut deps, VOTER1, Uint128::new(100));
+
+    let (env, _, response) = will_success(&mut deps, VOTER1, VOTER2);
+    assert_eq!(response.attributes, vec![
+        attr("action", "delegate"),
+        attr("delegator", VOTER1),
+        attr("delegate", VOTER2),
+    ]);
+
+    let delegator = Addr::unchecked(VOTER1);
+    let delegate = Addr::unchecked(VOTER2);
+
+    assert_eq!(load_delegate(&deps.storage, &delegator, None).unwrap(), Some(delegate.clone()));
+    assert_eq!(load_delegate(&deps.storage, &delegator, Some(env.block.height)).unwrap(), None);
+    assert_eq!(load_delegators(&deps.storage, &delegate, Some(env.block.height + 1)).unwrap(), vec![delegator]);
+}
+
+#[test]
+fn succeed_redelegate() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+    with_balance(&mut deps, VOTER1, Uint128::new(100));
+
+    will_success(&mut deps, VOTER1, VOTER2);
+    let (_, _, response) = will_success(&mut deps, VOTER1, VOTER3);
+    assert_eq!(response.attributes, vec![
+        attr("action", "delegate"),
+        attr("delegator", VOTER1),
+        attr("delegate", VOTER3),
+        attr("prev_delegate", VOTER2),
+    ]);
+
+    assert!(load_delegators(&deps.storage, &Addr::unchecked(VOTER2), None).unwrap().is_empty());
+    assert_eq!(
+        load_delegators(&deps.storage, &Addr::unchecked(VOTER3), None).unwrap(),
+        vec![Addr::unchecked(VOTER1)],
+    );
+}
+
+#[test]
+fn succeed_delegate_votes_with_delegated_power() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER1, Uint128::new(100));
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER2, Uint128::new(50));
+    will_success(&mut deps, VOTER1, VOTER2);
+
+    crate::poll::tests::create_poll::default(&mut deps);
+
+    let poll_id = 1u64;
+
+    crate::poll::tests::cast_vote::will_success(&mut deps, VOTER2, poll_id, VoteOption::Yes, Uint128::new(150));
+
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    assert_eq!(poll.yes_votes, Uint128::new(150));
+
+    let vote = poll.load_voter(&deps.storage, &Addr::unchecked(VOTER2)).unwrap();
+    assert_eq!(vote.delegated_amount, Uint128::new(100));
+
+    let staker_state = StakerState::load_safe(&deps.storage, &Addr::unchecked(VOTER2)).unwrap();
+    assert_eq!(staker_state.get_vote_locked_balance(), Uint128::new(50));
+}
+
+#[test]
+fn succeed_delegator_overrides_delegate() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER1, Uint128::new(100));
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER2, Uint128::new(50));
+    will_success(&mut deps, VOTER1, VOTER2);
+
+    crate::poll::tests::create_poll::default(&mut deps);
+
+    let poll_id = 1u64;
+
+    crate::poll::tests::cast_vote::will_success(&mut deps, VOTER2, poll_id, VoteOption::Yes, Uint128::new(150));
+    let (_, _, response) = crate::poll::tests::cast_vote::will_success(&mut deps, VOTER1, poll_id, VoteOption::No, Uint128::new(100));
+    assert!(response.attributes.contains(&attr("overridden_delegate", VOTER2)));
+
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    assert_eq!(poll.yes_votes, Uint128::new(50));
+    assert_eq!(poll.no_votes, Uint128::new(100));
+
+    let vote = poll.load_voter(&deps.storage, &Addr::unchecked(VOTER2)).unwrap();
+    assert_eq!(vote.amount, Uint128::new(50));
+    assert_eq!(vote.delegated_amount, Uint128::zero());
+}
+
+#[test]
+fn failed_delegate_to_self() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    let result = exec(&mut deps, governance_env(), mock_info(VOTER1, &[]), VOTER1);
+
+    expect_generic_err(&result, "Can not delegate to yourself");
+}
+
+#[test]
+fn failed_without_balance() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+    with_balance(&mut deps, VOTER1, Uint128::zero());
+
+    let result = exec(&mut deps, governance_env(), mock_info(VOTER1, &[]), VOTER2);
+
+    expect_generic_err(&result, "Insufficient balance to delegate");
+}
+
+fn fill_delegators(deps: &mut CustomDeps, delegate_addr: &str) -> Vec<String> {
+    (0..crate::delegation::states::MAX_DELEGATORS)
+        .map(|index| {
+            let delegator = format!("delegator{}", index);
+            with_balance(deps, &delegator, Uint128::new(1));
+            will_success(deps, &delegator, delegate_addr);
+            delegator
+        })
+        .collect()
+}
+
+#[test]
+fn succeed_redelegate_at_limit() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    let delegators = fill_delegators(&mut deps, VOTER1);
+
+    let (_, _, response) = will_success(&mut deps, &delegators[0], VOTER1);
+    assert!(response.attributes.contains(&attr("prev_delegate", VOTER1)));
+    assert_eq!(
+        load_delegators(&deps.storage, &Addr::unchecked(VOTER1), None).unwrap().len(),
+        crate::delegation::states::MAX_DELEGATORS,
+    );
+}
+
+#[test]
+fn succeed_prune_empty_delegators_at_limit() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    let delegators = fill_delegators(&mut deps, VOTER1);
+    deps.querier.minus_token_balances(&[(
+        GOVERNANCE_TOKEN,
+        &[(delegators[0].as_str(), &Uint128::new(1))],
+    )]);
+
+    with_balance(&mut deps, VOTER2, Uint128::new(100));
+    let (_, _, response) = will_success(&mut deps, VOTER2, VOTER1);
+    assert!(response.attributes.contains(&attr("pruned_delegator", delegators[0].as_str())));
+
+    let current_delegators = load_delegators(&deps.storage, &Addr::unchecked(VOTER1), None).unwrap();
+    assert_eq!(current_delegators.len(), crate::delegation::states::MAX_DELEGATORS);
+    assert!(current_delegators.contains(&Addr::unchecked(VOTER2)));
+    assert_eq!(load_delegate(&deps.storage, &Addr::unchecked(&delegators[0]), None).unwrap(), None);
+}
+
+#[test]
+fn failed_exceed_limit() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    fill_delegators(&mut deps, VOTER1);
+
+    with_balance(&mut deps, VOTER2, Uint128::new(100));
+    let result = exec(&mut deps, governance_env(), mock_info(VOTER2, &[]), VOTER1);
+
+    assert_eq!(result, Err(ContractError::ExceedLimit {}));
+}
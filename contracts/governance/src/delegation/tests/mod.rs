@@ -0,0 +1,2 @@
+pub mod delegate;
+pub mod undelegate;
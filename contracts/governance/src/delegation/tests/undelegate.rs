@@ -0,0 +1,47 @@
+use cosmwasm_std::{Addr, attr, Env, MessageInfo, Response, Uint128};
+use cosmwasm_std::testing::mock_info;
+
+use terrapoker::common::ContractResult;
+use terrapoker::mock_querier::{custom_deps, CustomDeps};
+use terrapoker::test_constants::governance::governance_env;
+use terrapoker::test_utils::expect_generic_err;
+
+use crate::delegation::executions::undelegate;
+use crate::delegation::states::{load_delegate, load_delegators};
+use crate::poll::tests::cast_vote::{VOTER1, VOTER2};
+use crate::tests::init_default;
+
+pub fn exec(deps: &mut CustomDeps, env: Env, info: MessageInfo) -> ContractResult<Response> {
+    undelegate(deps.as_mut(), env, info)
+}
+
+#[test]
+fn succeed() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+    super::delegate::with_balance(&mut deps, VOTER1, Uint128::new(100));
+
+    super::delegate::will_success(&mut deps, VOTER1, VOTER2);
+
+    let response = exec(&mut deps, governance_env(), mock_info(VOTER1, &[])).unwrap();
+    assert_eq!(response.attributes, vec![
+        attr("action", "undelegate"),
+        attr("delegator", VOTER1),
+        attr("prev_delegate", VOTER2),
+    ]);
+
+    assert_eq!(load_delegate(&deps.storage, &Addr::unchecked(VOTER1), None).unwrap(), None);
+    assert!(load_delegators(&deps.storage, &Addr::unchecked(VOTER2), None).unwrap().is_empty());
+}
+
+#[test]
+fn failed_not_delegated() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    let result = exec(&mut deps, governance_env(), mock_info(VOTER1, &[]));
+
+    expect_generic_err(&result, "Not delegated");
+}
@@ -4,12 +4,16 @@ use cosmwasm_std::{
     from_binary, to_binary, Addr, Binary, Deps, DepsMut, Env, MessageInfo, Reply, Response,
     StdError,
 };
+use cw2::{get_contract_version, set_contract_version};
 use cw20::Cw20ReceiveMsg;
 use terrapoker::common::ContractResult;
 use terrapoker::errors::ContractError;
 use terrapoker::governance::execute_msgs::{Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg};
 use terrapoker::governance::query_msgs::QueryMsg;
 
+const CONTRACT_NAME: &str = "tp-governance";
+const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
     mut deps: DepsMut,
@@ -17,6 +21,8 @@ pub fn instantiate(
     info: MessageInfo,
     msg: InstantiateMsg,
 ) -> ContractResult<Response> {
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
     crate::common::executions::instantiate(
         deps.branch(),
         env.clone(),
@@ -51,28 +57,43 @@ pub fn execute(
         ExecuteMsg::UpdateStakingConfig {
             distributor,
             unstake_lock_period,
+            instant_unstake_fee,
+            instant_unstake_fee_recipient,
+            clear_instant_unstake_fee_recipient,
         } => crate::staking::executions::update_staking_config(
             deps,
             env,
             info,
             distributor,
             unstake_lock_period,
+            instant_unstake_fee,
+            instant_unstake_fee_recipient,
+            clear_instant_unstake_fee_recipient,
         ),
-        ExecuteMsg::UpdatePollConfig {
-            quorum,
-            threshold,
-            voting_period,
-            execution_delay_period,
-            proposal_deposit,
-        } => crate::poll::executions::update_poll_config(
+        ExecuteMsg::UpdatePollConfig(msg) => crate::poll::executions::update_poll_config(
             deps,
             env,
             info,
-            quorum,
-            threshold,
-            voting_period,
-            execution_delay_period,
-            proposal_deposit,
+            msg.quorum,
+            msg.threshold,
+            msg.voting_period,
+            msg.execution_delay_period,
+            msg.proposal_deposit,
+            msg.deposit_period,
+            msg.veto_threshold,
+            msg.veto_deposit_recipient,
+            msg.clear_veto_deposit_recipient,
+            msg.cancel_slash_rate,
+            msg.execution_expiry_period,
+            msg.execution_policy,
+            msg.expedited_voting_period,
+            msg.expedited_threshold,
+            msg.expedited_proposal_deposit,
+            msg.early_finalization,
+            msg.min_proposer_balance,
+            msg.max_polls_per_proposer,
+            msg.proposal_cooldown_period,
+            msg.quorum_base,
         ),
         ExecuteMsg::StakeGovernanceTokenHook {
             staker,
@@ -86,21 +107,65 @@ pub fn execute(
         } => crate::staking::executions::unstake_token_hook(
             deps, env, info, staker, amount,
         ),
-        ExecuteMsg::ClaimUnstakedToken {} => crate::staking::executions::claim_unstaked_token(deps, env, info),
+        ExecuteMsg::InstantUnstakeGovernanceTokenHook {
+            staker,
+            amount,
+        } => crate::staking::executions::instant_unstake_token_hook(
+            deps, env, info, staker, amount,
+        ),
+        ExecuteMsg::ClaimUnstakedToken {
+            limit,
+            recipient,
+            message,
+        } => crate::staking::executions::claim_unstaked_token(deps, env, info, limit, recipient, message),
+        ExecuteMsg::CancelUnstake { height } => crate::staking::executions::cancel_unstake(deps, env, info, height),
         ExecuteMsg::CastVote {
             poll_id,
             vote,
             amount,
         } => crate::poll::executions::cast_vote(deps, env, info, poll_id, vote, amount),
+        ExecuteMsg::CastSplitVote {
+            poll_id,
+            votes,
+        } => crate::poll::executions::cast_split_vote(deps, env, info, poll_id, votes),
+        ExecuteMsg::RetractVote {
+            poll_id,
+        } => crate::poll::executions::retract_vote(deps, env, info, poll_id),
+        ExecuteMsg::Delegate {
+            delegate,
+        } => crate::delegation::executions::delegate(deps, env, info, delegate),
+        ExecuteMsg::Undelegate {} => crate::delegation::executions::undelegate(deps, env, info),
+        ExecuteMsg::CancelPoll {
+            poll_id,
+        } => crate::poll::executions::cancel_poll(deps, env, info, poll_id),
         ExecuteMsg::EndPoll {
             poll_id,
         } => crate::poll::executions::end_poll(deps, env, info, poll_id),
         ExecuteMsg::ExecutePoll {
             poll_id,
         } => crate::poll::executions::execute_poll(deps, env, info, poll_id),
+        ExecuteMsg::ExpirePoll {
+            poll_id,
+        } => crate::poll::executions::expire_poll(deps, env, info, poll_id),
+        ExecuteMsg::VetoPoll {
+            poll_id,
+        } => crate::poll::executions::veto_poll(deps, env, info, poll_id),
+        ExecuteMsg::UpdateGuardian {
+            guardian,
+        } => crate::poll::executions::update_guardian(deps, env, info, guardian),
         ExecuteMsg::RunExecution {
             executions,
         } => crate::poll::executions::run_execution(deps, env, info, executions),
+        ExecuteMsg::AllocateVotingReward {
+            poll_id,
+            amount,
+        } => crate::poll::executions::allocate_voting_reward(deps, env, info, poll_id, amount),
+        ExecuteMsg::ClaimVotingRewards {
+            poll_ids,
+        } => crate::poll::executions::claim_voting_rewards(deps, env, info, poll_ids),
+        ExecuteMsg::MigrateLegacyState {
+            limit,
+        } => crate::migrations::migrate_legacy_state(deps, env, info, limit),
     }
 }
 
@@ -123,6 +188,10 @@ pub fn receive_cw20(
             description,
             link,
             executions,
+            atomic,
+            kind,
+            expedited,
+            actions,
         } => crate::poll::executions::create_poll(
             deps,
             env,
@@ -133,8 +202,40 @@ pub fn receive_cw20(
             description,
             link,
             executions,
+            atomic,
+            kind,
+            expedited,
+            actions,
+        ),
+        Cw20HookMsg::UnstakeGovernanceToken {} => crate::staking::executions::unstake_token(deps, env, info, Addr::unchecked(cw20_msg.sender), cw20_msg.amount, false),
+        Cw20HookMsg::InstantUnstake {} => crate::staking::executions::unstake_token(deps, env, info, Addr::unchecked(cw20_msg.sender), cw20_msg.amount, true),
+        Cw20HookMsg::Deposit {
+            poll_id,
+        } => crate::poll::executions::deposit(
+            deps,
+            env,
+            info,
+            Addr::unchecked(cw20_msg.sender),
+            cw20_msg.amount,
+            poll_id,
+        ),
+        Cw20HookMsg::StakingReward {} => crate::staking::executions::receive_staking_reward(
+            deps,
+            env,
+            info,
+            Addr::unchecked(cw20_msg.sender),
+            cw20_msg.amount,
+        ),
+        Cw20HookMsg::FundVotingReward {
+            poll_id,
+        } => crate::poll::executions::fund_voting_reward(
+            deps,
+            env,
+            info,
+            Addr::unchecked(cw20_msg.sender),
+            cw20_msg.amount,
+            poll_id,
         ),
-        Cw20HookMsg::UnstakeGovernanceToken {} => crate::staking::executions::unstake_token(deps, env, info, Addr::unchecked(cw20_msg.sender), cw20_msg.amount),
     }
 }
 
@@ -144,12 +245,28 @@ pub fn reply(deps: DepsMut, env: Env, msg: Reply) -> ContractResult<Response> {
         crate::poll::executions::REPLY_EXECUTION => {
             crate::poll::executions::reply_execution(deps, env, msg)
         }
+        id if id >= crate::poll::executions::REPLY_EXECUTION_RESULT => {
+            crate::poll::executions::reply_execution_result(deps, env, msg)
+        }
         _ => Err(ContractError::Std(StdError::not_found("reply_id"))),
     }
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> ContractResult<Response> {
+pub fn migrate(mut deps: DepsMut, env: Env, _msg: MigrateMsg) -> ContractResult<Response> {
+    match get_contract_version(deps.storage) {
+        // Deployed before the version was recorded
+        Err(_) => crate::migrations::migrate(deps.branch(), &env)?,
+        Ok(version) if version.contract != CONTRACT_NAME => {
+            return Err(ContractError::Std(StdError::generic_err(
+                format!("Can not migrate from {}", version.contract),
+            )));
+        }
+        Ok(_) => {}
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
     Ok(Response::default())
 }
 
@@ -173,6 +290,31 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> ContractResult<Binary> {
             limit,
             order_by,
         )?),
+        QueryMsg::PollExecutionResults {
+            poll_id,
+        } => to_binary(&crate::poll::queries::query_poll_execution_results(
+            deps,
+            env,
+            poll_id,
+        )?),
+        QueryMsg::PollTally {
+            poll_id,
+        } => to_binary(&crate::poll::queries::query_poll_tally(
+            deps,
+            env,
+            poll_id,
+        )?),
+        QueryMsg::PollDeposits {
+            poll_id,
+            start_after,
+            limit,
+        } => to_binary(&crate::poll::queries::query_poll_deposits(
+            deps,
+            env,
+            poll_id,
+            start_after,
+            limit,
+        )?),
         QueryMsg::Voters {
             poll_id,
             start_after,
@@ -186,11 +328,27 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> ContractResult<Binary> {
             limit,
             order_by,
         )?),
+        QueryMsg::Delegation { address } => to_binary(&crate::delegation::queries::get_delegation(
+            deps, env, address,
+        )?),
+        QueryMsg::DelegatedPower {
+            address,
+            height,
+        } => to_binary(&crate::delegation::queries::get_delegated_power(
+            deps, env, address, height,
+        )?),
         QueryMsg::StakingConfig {} => to_binary(&crate::staking::queries::get_staking_config(deps, env)?),
         QueryMsg::StakingState {} => to_binary(&crate::staking::queries::get_staking_state(deps, env)?),
         QueryMsg::StakerState { address } => to_binary(&crate::staking::queries::get_staker_state(
             deps, env, address,
         )?),
+        QueryMsg::StakerVotes {
+            address,
+            start_after,
+            limit,
+        } => to_binary(&crate::staking::queries::query_staker_votes(
+            deps, env, address, start_after, limit,
+        )?),
         QueryMsg::AllStaker {
             start_after,
             limit,
@@ -204,6 +362,22 @@ pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> ContractResult<Binary> {
         QueryMsg::SimulateUnstakeAmount { amount } => to_binary(&crate::staking::queries::get_simulate_unstake_amount(
             deps, env, amount,
         )?),
+        QueryMsg::UnstakeRequests {
+            address,
+            start_after,
+            limit,
+        } => to_binary(&crate::staking::queries::get_unstake_requests(deps, env, address, start_after, limit)?),
+        QueryMsg::ExchangeRate { height } => to_binary(&crate::staking::queries::get_exchange_rate(
+            deps, env, height,
+        )?),
+        QueryMsg::ExchangeRateHistory {
+            start_after,
+            limit,
+        } => to_binary(&crate::staking::queries::get_exchange_rate_history(deps, env, start_after, limit)?),
+        QueryMsg::StakingApr {
+            window,
+            blocks_per_year,
+        } => to_binary(&crate::staking::queries::get_staking_apr(deps, env, window, blocks_per_year)?),
     }?;
 
     Ok(result)
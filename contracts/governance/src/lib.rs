@@ -3,6 +3,8 @@ pub mod entrypoints;
 mod common;
 mod staking;
 mod poll;
+mod delegation;
+mod migrations;
 
 #[cfg(test)]
 mod tests;
@@ -0,0 +1,146 @@
+use cosmwasm_std::{Addr, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Storage};
+use cw_storage_plus::Item;
+use serde::{Deserialize, Serialize};
+use schemars::JsonSchema;
+
+use terrapoker::common::ContractResult;
+use terrapoker::errors::ContractError;
+use terrapoker::utils::make_response;
+
+use crate::common::states::ContractConfig;
+use crate::poll::states::{ExecutionPolicy, Poll, PollConfig};
+use crate::staking::states::StakerState;
+
+const DEFAULT_BATCH_SIZE: u32 = 30;
+const MAX_BATCH_SIZE: u32 = 100;
+
+// Starts moving the states stored before the contract version was recorded and migrates the first batch.
+// The rest is migrated by MigrateLegacyState so that a single transaction stays bounded.
+pub fn migrate(
+    deps: DepsMut,
+    _env: &Env,
+) -> StdResult<()> {
+    migrate_poll_config(deps.storage)?;
+
+    LegacyMigration {
+        staker_start_after: None,
+        stakers_migrated: false,
+        voter_start_after: None,
+    }.save(deps.storage)?;
+
+    migrate_batch(deps, DEFAULT_BATCH_SIZE)?;
+
+    Ok(())
+}
+
+// Fills the poll config fields added since, keeping the execution checks of the legacy contract.
+fn migrate_poll_config(storage: &mut dyn Storage) -> StdResult<()> {
+    let contract_config = ContractConfig::load(storage)?;
+    let mut poll_config = PollConfig::load(storage)?;
+
+    poll_config.execution_policy = ExecutionPolicy {
+        allow_any_target: true,
+        targets: vec![],
+    };
+    poll_config.execution_policy.deny_token_transfers(&[&contract_config.staking_token, &contract_config.governance_token]);
+
+    // expedited polls must not be cheaper to open than normal ones
+    poll_config.expedited_proposal_deposit = poll_config.expedited_proposal_deposit.max(poll_config.proposal_deposit);
+
+    poll_config.save(storage)
+}
+
+pub fn migrate_legacy_state(
+    deps: DepsMut,
+    _env: Env,
+    _info: MessageInfo,
+    limit: Option<u32>,
+) -> ContractResult<Response> {
+    // Validate
+    if LegacyMigration::may_load(deps.storage)?.is_none() {
+        return Err(ContractError::Std(StdError::generic_err("Nothing to migrate")));
+    }
+
+    // Execute
+    let mut response = make_response("migrate_legacy_state");
+
+    let limit = limit.unwrap_or(DEFAULT_BATCH_SIZE).min(MAX_BATCH_SIZE);
+    let (migrated, completed) = migrate_batch(deps, limit)?;
+
+    response = response.add_attribute("migrated", migrated.to_string());
+    response = response.add_attribute("completed", completed.to_string());
+
+    Ok(response)
+}
+
+// Migrates up to `limit` entries and returns their number and whether the migration is completed.
+fn migrate_batch(deps: DepsMut, limit: u32) -> StdResult<(u32, bool)> {
+    let mut migration = LegacyMigration::load(deps.storage)?;
+
+    let mut migrated = 0u32;
+    if !migration.stakers_migrated {
+        let staker_states = StakerState::load_all(
+            deps.as_ref(),
+            migration.staker_start_after.as_ref().map(|a| a.to_string()),
+            Some(limit),
+        )?;
+
+        migrated = staker_states.len() as u32;
+        for mut staker_state in staker_states {
+            staker_state.migrate_unstake_locked(deps.storage)?;
+            staker_state.save(deps.storage)?;
+
+            migration.staker_start_after = Some(staker_state.address);
+        }
+
+        migration.stakers_migrated = migrated < limit;
+    }
+
+    // Votes cast before the staker voting history existed
+    if migration.stakers_migrated && migrated < limit {
+        let (copied, last_key) = Poll::backfill_staker_votes(
+            deps.storage,
+            migration.voter_start_after.as_ref(),
+            limit - migrated,
+        )?;
+
+        if copied < limit - migrated {
+            LegacyMigration::remove(deps.storage);
+            return Ok((migrated + copied, true));
+        }
+
+        migrated += copied;
+        migration.voter_start_after = last_key;
+    }
+
+    migration.save(deps.storage)?;
+
+    Ok((migrated, false))
+}
+
+const LEGACY_MIGRATION: Item<LegacyMigration> = Item::new("legacy-migration");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyMigration {
+    pub staker_start_after: Option<Addr>, // last staker state moved to the new layout
+    pub stakers_migrated: bool,
+    pub voter_start_after: Option<(u64, Addr)>, // last poll voter copied to the staker voting history
+}
+
+impl LegacyMigration {
+    pub fn save(&self, storage: &mut dyn Storage) -> StdResult<()> {
+        LEGACY_MIGRATION.save(storage, self)
+    }
+
+    pub fn load(storage: &dyn Storage) -> StdResult<LegacyMigration> {
+        LEGACY_MIGRATION.load(storage)
+    }
+
+    pub fn may_load(storage: &dyn Storage) -> StdResult<Option<LegacyMigration>> {
+        LEGACY_MIGRATION.may_load(storage)
+    }
+
+    pub fn remove(storage: &mut dyn Storage) {
+        LEGACY_MIGRATION.remove(storage)
+    }
+}
@@ -1,18 +1,21 @@
-use cosmwasm_std::{Addr, Decimal, DepsMut, Env, from_binary, MessageInfo, Reply, ReplyOn, Response, StdError, StdResult, SubMsg, Uint128};
+use cosmwasm_std::{Addr, CosmosMsg, Decimal, DepsMut, Env, MessageInfo, Reply, ReplyOn, Response, StdError, StdResult, Storage, SubMsg, SubMsgResult, Uint128};
 use cw20::Cw20ExecuteMsg;
 
-use terrapoker::common::{ContractResult, Execution, ExecutionMsg};
+use terrapoker::common::{ContractResult, Execution, ExecutionMsg, parse_message_name};
 use terrapoker::errors::ContractError;
-use terrapoker::governance::enumerations::{PollStatus, VoteOption};
+use terrapoker::governance::actions::GovActionMsg;
+use terrapoker::governance::enumerations::{DepositOutcome, PollKind, PollStatus, TallyRule, VoteOption};
 use terrapoker::governance::execute_msgs::{ExecuteMsg, PollConfigInitMsg};
+use terrapoker::governance::models::{ExecutionPolicyMsg, GuardianMsg, QuorumBaseMsg};
 use terrapoker::message_factories;
-use terrapoker::utils::make_response;
+use terrapoker::utils::{addr_opt_validate, make_response, validate_zero_to_one};
 
-use crate::common::states::{ContractConfig, load_gov_token_balance, load_gov_token_total_supply};
-use crate::poll::states::{PollExecutionContext, PollResult};
-use crate::staking::states::{StakerState};
+use crate::common::states::{ContractConfig, load_gov_token_balance};
+use crate::delegation::states::{load_delegate, load_delegated_power};
+use crate::poll::states::{ExecutionPolicy, Guardian, QuorumBase, ExecutionResult, PollExecutionContext, PollResult};
+use crate::staking::states::{checkpoint_exchange_rate, StakerState, StakingState};
 
-use super::states::{get_poll_id, Poll, PollConfig, PollState};
+use super::states::{get_poll_id, Poll, PollConfig, PollState, ProposerState};
 
 const MIN_TITLE_LENGTH: usize = 4;
 const MAX_TITLE_LENGTH: usize = 64;
@@ -20,10 +23,13 @@ const MIN_DESC_LENGTH: usize = 4;
 const MAX_DESC_LENGTH: usize = 1024;
 const MIN_LINK_LENGTH: usize = 12;
 const MAX_LINK_LENGTH: usize = 128;
+const MIN_POLL_OPTIONS: usize = 2;
+const MAX_POLL_OPTIONS: usize = 10;
+const MAX_POLL_OPTION_LENGTH: usize = 64;
 
 pub fn instantiate(
     deps: DepsMut,
-    _env: Env,
+    env: Env,
     _info: MessageInfo,
     msg: PollConfigInitMsg,
 ) -> ContractResult<Response> {
@@ -31,6 +37,21 @@ pub fn instantiate(
     validate_quorum(msg.quorum)?;
     validate_threshold(msg.threshold)?;
     validate_execution_delay_period(msg.execution_delay_period)?;
+    validate_veto_threshold(msg.veto_threshold)?;
+    validate_zero_to_one(msg.cancel_slash_rate, "cancel_slash_rate")?;
+    validate_threshold(msg.expedited_threshold)?;
+    validate_expedited(
+        msg.expedited_voting_period,
+        msg.expedited_threshold,
+        msg.expedited_proposal_deposit,
+        msg.voting_period,
+        msg.threshold,
+        msg.proposal_deposit,
+    )?;
+
+    let contract_config = ContractConfig::load(deps.storage)?;
+    let mut execution_policy = ExecutionPolicy::from_msg(deps.api, &msg.execution_policy)?;
+    execution_policy.deny_token_transfers(&[&contract_config.staking_token, &contract_config.governance_token]);
 
     // Execute
     let response = make_response("instantiate");
@@ -41,6 +62,21 @@ pub fn instantiate(
         voting_period: msg.voting_period,
         execution_delay_period: msg.execution_delay_period,
         proposal_deposit: msg.proposal_deposit,
+        deposit_period: msg.deposit_period,
+        veto_threshold: msg.veto_threshold,
+        veto_deposit_recipient: addr_opt_validate(deps.api, &msg.veto_deposit_recipient)?,
+        cancel_slash_rate: msg.cancel_slash_rate,
+        execution_expiry_period: msg.execution_expiry_period,
+        execution_policy,
+        expedited_voting_period: msg.expedited_voting_period,
+        expedited_threshold: msg.expedited_threshold,
+        expedited_proposal_deposit: msg.expedited_proposal_deposit,
+        early_finalization: msg.early_finalization,
+        min_proposer_balance: msg.min_proposer_balance,
+        max_polls_per_proposer: msg.max_polls_per_proposer,
+        proposal_cooldown_period: msg.proposal_cooldown_period,
+        quorum_base: QuorumBase::from_msg(deps.api, &msg.quorum_base)?,
+        guardian: msg.guardian.map(|g| Guardian::from_msg(deps.api, &g, env.block.height)).transpose()?,
     };
 
     let poll_state = PollState {
@@ -64,12 +100,32 @@ pub fn update_poll_config(
     voting_period: Option<u64>,
     execution_delay_period: Option<u64>,
     proposal_deposit: Option<Uint128>,
+    deposit_period: Option<u64>,
+    veto_threshold: Option<Decimal>,
+    veto_deposit_recipient: Option<String>,
+    clear_veto_deposit_recipient: Option<bool>,
+    cancel_slash_rate: Option<Decimal>,
+    execution_expiry_period: Option<u64>,
+    execution_policy: Option<ExecutionPolicyMsg>,
+    expedited_voting_period: Option<u64>,
+    expedited_threshold: Option<Decimal>,
+    expedited_proposal_deposit: Option<Uint128>,
+    early_finalization: Option<bool>,
+    min_proposer_balance: Option<Uint128>,
+    max_polls_per_proposer: Option<u64>,
+    proposal_cooldown_period: Option<u64>,
+    quorum_base: Option<QuorumBaseMsg>,
 ) -> ContractResult<Response> {
     // Validate
     if env.contract.address != info.sender {
         return Err(ContractError::Unauthorized {});
     }
 
+    let clear_veto_deposit_recipient = clear_veto_deposit_recipient.unwrap_or(false);
+    if clear_veto_deposit_recipient && veto_deposit_recipient.is_some() {
+        return Err(ContractError::Std(StdError::generic_err("Can not set and clear veto_deposit_recipient at once")));
+    }
+
     // Execute
     let mut response = make_response("update_poll_config");
 
@@ -103,6 +159,96 @@ pub fn update_poll_config(
         response = response.add_attribute("is_updated_proposal_deposit", "true");
     }
 
+    if let Some(deposit_period) = deposit_period {
+        poll_config.deposit_period = deposit_period;
+        response = response.add_attribute("is_updated_deposit_period", "true");
+    }
+
+    if let Some(veto_threshold) = veto_threshold {
+        validate_veto_threshold(veto_threshold)?;
+        poll_config.veto_threshold = veto_threshold;
+        response = response.add_attribute("is_updated_veto_threshold", "true");
+    }
+
+    if let Some(veto_deposit_recipient) = veto_deposit_recipient {
+        poll_config.veto_deposit_recipient = Some(deps.api.addr_validate(&veto_deposit_recipient)?);
+        response = response.add_attribute("is_updated_veto_deposit_recipient", "true");
+    }
+
+    if clear_veto_deposit_recipient {
+        poll_config.veto_deposit_recipient = None;
+        response = response.add_attribute("is_updated_veto_deposit_recipient", "true");
+    }
+
+    if let Some(cancel_slash_rate) = cancel_slash_rate {
+        validate_zero_to_one(cancel_slash_rate, "cancel_slash_rate")?;
+        poll_config.cancel_slash_rate = cancel_slash_rate;
+        response = response.add_attribute("is_updated_cancel_slash_rate", "true");
+    }
+
+    if let Some(execution_expiry_period) = execution_expiry_period {
+        poll_config.execution_expiry_period = execution_expiry_period;
+        response = response.add_attribute("is_updated_execution_expiry_period", "true");
+    }
+
+    if let Some(execution_policy) = execution_policy {
+        poll_config.execution_policy = ExecutionPolicy::from_msg(deps.api, &execution_policy)?;
+        response = response.add_attribute("is_updated_execution_policy", "true");
+    }
+
+    if let Some(expedited_voting_period) = expedited_voting_period {
+        poll_config.expedited_voting_period = expedited_voting_period;
+        response = response.add_attribute("is_updated_expedited_voting_period", "true");
+    }
+
+    if let Some(expedited_threshold) = expedited_threshold {
+        validate_threshold(expedited_threshold)?;
+        poll_config.expedited_threshold = expedited_threshold;
+        response = response.add_attribute("is_updated_expedited_threshold", "true");
+    }
+
+    if let Some(expedited_proposal_deposit) = expedited_proposal_deposit {
+        poll_config.expedited_proposal_deposit = expedited_proposal_deposit;
+        response = response.add_attribute("is_updated_expedited_proposal_deposit", "true");
+    }
+
+    if let Some(early_finalization) = early_finalization {
+        poll_config.early_finalization = early_finalization;
+        response = response.add_attribute("is_updated_early_finalization", "true");
+    }
+
+    if let Some(min_proposer_balance) = min_proposer_balance {
+        poll_config.min_proposer_balance = min_proposer_balance;
+        response = response.add_attribute("is_updated_min_proposer_balance", "true");
+    }
+
+    if let Some(max_polls_per_proposer) = max_polls_per_proposer {
+        poll_config.max_polls_per_proposer = max_polls_per_proposer;
+        response = response.add_attribute("is_updated_max_polls_per_proposer", "true");
+    }
+
+    if let Some(proposal_cooldown_period) = proposal_cooldown_period {
+        poll_config.proposal_cooldown_period = proposal_cooldown_period;
+        response = response.add_attribute("is_updated_proposal_cooldown_period", "true");
+    }
+
+    if let Some(quorum_base) = quorum_base {
+        poll_config.quorum_base = QuorumBase::from_msg(deps.api, &quorum_base)?;
+        response = response.add_attribute("is_updated_quorum_base", "true");
+    }
+
+    // Configs stored before expedited polls existed keep them disabled until a period is set
+    if poll_config.expedited_voting_period != 0 || expedited_voting_period.is_some() {
+        validate_expedited(
+            poll_config.expedited_voting_period,
+            poll_config.expedited_threshold,
+            poll_config.expedited_proposal_deposit,
+            poll_config.voting_period,
+            poll_config.threshold,
+            poll_config.proposal_deposit,
+        )?;
+    }
+
     poll_config.save(deps.storage)?;
 
     Ok(response)
@@ -119,23 +265,65 @@ pub fn create_poll(
     description: String,
     link: Option<String>,
     executions: Vec<ExecutionMsg>,
+    atomic: Option<bool>,
+    kind: Option<PollKind>,
+    expedited: Option<bool>,
+    actions: Option<Vec<GovActionMsg>>,
 ) -> ContractResult<Response> {
     // Validate
+    let kind = kind.unwrap_or_default();
+    let expedited = expedited.unwrap_or(false);
+    let actions = actions.unwrap_or_default();
+
+    let mut executions = executions;
+    for action in actions.iter() {
+        executions.push(action.to_execution_msg(env.contract.address.as_str())?);
+    }
+
     validate_title(&title)?;
     validate_description(&description)?;
     validate_link(&link)?;
     validate_executions(&executions)?;
+    validate_poll_kind(&kind, &executions)?;
+
+    if expedited && matches!(kind, PollKind::MultipleChoice { .. }) {
+        return Err(ContractError::Std(StdError::generic_err("Multiple choice poll can not be expedited")));
+    }
 
     let config = ContractConfig::load(deps.storage)?;
     if !config.is_governance_token(&info.sender) {
         return Err(ContractError::Unauthorized {});
     }
 
+    if deposit_amount.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err("Must deposit more than 0 token")));
+    }
+
     let poll_config = PollConfig::load(deps.storage)?;
-    if deposit_amount < poll_config.proposal_deposit {
-        return Err(ContractError::Std(StdError::generic_err(
-            format!("Must deposit more than {} token", poll_config.proposal_deposit)
-        )));
+
+    if expedited && poll_config.expedited_voting_period == 0 {
+        return Err(ContractError::Std(StdError::generic_err("Expedited polls are not enabled")));
+    }
+
+    if !poll_config.min_proposer_balance.is_zero() {
+        let proposer_balance = load_gov_token_balance(deps.as_ref(), &proposer, Some(env.block.height - 1))?;
+        if proposer_balance < poll_config.min_proposer_balance {
+            return Err(ContractError::Std(StdError::generic_err("Insufficient balance to create a poll")));
+        }
+    }
+
+    let mut proposer_state = ProposerState::load_safe(deps.storage, &proposer)?;
+    proposer_state.clean_open_polls(deps.storage);
+
+    if poll_config.max_polls_per_proposer != 0
+        && proposer_state.open_polls.len() as u64 >= poll_config.max_polls_per_proposer {
+        return Err(ContractError::Std(StdError::generic_err("Too many open polls")));
+    }
+
+    if let Some(last_poll_height) = proposer_state.last_poll_height {
+        if env.block.height < last_poll_height + poll_config.proposal_cooldown_period {
+            return Err(ContractError::Std(StdError::generic_err("Poll creation is in cooldown")));
+        }
     }
 
     // Execute
@@ -145,30 +333,131 @@ pub fn create_poll(
         .map(|execution| Execution::from(deps.api, execution))
         .collect::<StdResult<Vec<Execution>>>()?;
 
+    for execution in executions.iter() {
+        poll_config.execution_policy.validate(execution)?;
+    }
+
     let mut poll = Poll {
         id: get_poll_id(deps.storage, &deposit_amount)?,
         creator: proposer.clone(),
-        status: PollStatus::InProgress,
+        status: PollStatus::DepositPeriod,
         yes_votes: Uint128::zero(),
         no_votes: Uint128::zero(),
         abstain_votes: Uint128::zero(),
+        no_with_veto_votes: Uint128::zero(),
+        deposit_end_height: env.block.height + poll_config.deposit_period,
         start_height: env.block.height,
-        end_height: env.block.height + poll_config.voting_period,
+        end_height: 0,
         title,
         description,
         link,
         executions,
-        deposit_amount,
-        total_balance_at_start_poll: load_gov_token_total_supply(deps.as_ref(), Some(env.block.height - 1))?,
+        deposit_amount: Uint128::zero(),
+        total_balance_at_start_poll: Uint128::zero(),
+        atomic: atomic.unwrap_or(true),
+        choice_votes: match &kind {
+            PollKind::MultipleChoice { options, .. } => vec![Uint128::zero(); options.len()],
+            PollKind::Binary {} => vec![],
+        },
+        choice_voted_amount: Uint128::zero(),
+        winning_choice: None,
+        kind,
+        expedited,
+        finalized_early: false,
+        voting_reward: Uint128::zero(),
+        quorum_base: poll_config.quorum_base.clone(),
+        actions,
+        delegated_votes: Uint128::zero(),
+        settled_voting_reward: Uint128::zero(),
+        claimed_vote_amount: Uint128::zero(),
         _status: None,
     };
+    poll.end_height = env.block.height + poll.voting_period(&poll_config);
+
+    // Without a deposit period the whole deposit is required on creation
+    let required_deposit = poll.required_deposit(&poll_config);
+    if poll_config.deposit_period == 0 && deposit_amount < required_deposit {
+        return Err(ContractError::Std(StdError::generic_err(
+            format!("Must deposit more than {} token", required_deposit)
+        )));
+    }
+
+    poll.deposit(deps.storage, &proposer, deposit_amount)?;
+
+    // Voting starts right away if the proposer deposited enough
+    if poll.deposit_amount >= required_deposit {
+        poll.start_voting(deps.as_ref(), env.block.height, &poll_config)?;
+    }
 
     poll.save_with_index(deps.storage)?;
 
+    proposer_state.open_polls.push(poll.id);
+    proposer_state.last_poll_height = Some(env.block.height);
+    proposer_state.save(deps.storage)?;
+
     response = response.add_attribute("creator", proposer.as_str());
     response = response.add_attribute("poll_id", poll.id.to_string());
     response = response.add_attribute("end_height", poll.end_height.to_string());
 
+    if poll.expedited {
+        response = response.add_attribute("expedited", "true");
+    }
+
+    if poll.status == PollStatus::DepositPeriod {
+        response = response.add_attribute("deposit_end_height", poll.deposit_end_height.to_string());
+    }
+
+    Ok(response)
+}
+
+pub fn deposit(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    depositor: Addr,
+    amount: Uint128,
+    poll_id: u64,
+) -> ContractResult<Response> {
+    // Validate
+    let config = ContractConfig::load(deps.storage)?;
+    if !config.is_governance_token(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if amount.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err("Insufficient funds sent")));
+    }
+
+    let mut poll = Poll::may_load(deps.storage, &poll_id)?
+        .ok_or(ContractError::Std(StdError::generic_err("Poll does not exist")))?;
+
+    if !poll.in_deposit_period(env.block.height) {
+        return Err(ContractError::Std(StdError::generic_err("Poll is not in deposit period")));
+    }
+
+    // Execute
+    let mut response = make_response("deposit");
+
+    let poll_config = PollConfig::load(deps.storage)?;
+
+    poll.deposit(deps.storage, &depositor, amount)?;
+
+    if poll.deposit_amount >= poll.required_deposit(&poll_config) {
+        poll.start_voting(deps.as_ref(), env.block.height, &poll_config)?;
+    }
+
+    poll.save_with_index(deps.storage)?;
+
+    let mut poll_state = PollState::load(deps.storage)?;
+    poll_state.total_deposit += amount;
+    poll_state.save(deps.storage)?;
+
+    response = response.add_attribute("poll_id", poll_id.to_string());
+    response = response.add_attribute("depositor", depositor.as_str());
+    response = response.add_attribute("amount", amount.to_string());
+    response = response.add_attribute("deposit_amount", poll.deposit_amount.to_string());
+    response = response.add_attribute("poll_status", poll.status.to_string());
+
     Ok(response)
 }
 
@@ -177,10 +466,22 @@ pub fn cast_vote(
     env: Env,
     info: MessageInfo,
     poll_id: u64,
-    option: VoteOption,
+    vote: VoteOption,
     amount: Uint128,
+) -> ContractResult<Response> {
+    cast_split_vote(deps, env, info, poll_id, vec![(vote, amount)])
+}
+
+pub fn cast_split_vote(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    poll_id: u64,
+    votes: Vec<(VoteOption, Uint128)>,
 ) -> ContractResult<Response> {
     // Validate
+    validate_votes(&votes)?;
+
     let poll_state = PollState::load(deps.storage)?;
 
     if poll_id == 0 || poll_state.poll_count < poll_id {
@@ -193,22 +494,33 @@ pub fn cast_vote(
         return Err(ContractError::Std(StdError::generic_err("Poll is not in progress")));
     }
 
-    // Check the voter already has a vote on the poll
-    if poll.is_voted(deps.storage, &info.sender) {
-        return Err(ContractError::Std(StdError::generic_err("User has already voted.")));
+    validate_vote_options(&poll.kind, &votes)?;
+
+    let poll_config = PollConfig::load(deps.storage)?;
+    if poll_config.early_finalization && poll.may_load_voter(deps.storage, &info.sender)?.is_some() {
+        return Err(ContractError::Std(StdError::generic_err("Votes can not be changed while early finalization is enabled")));
     }
 
-    let gov_token_balance_at_height = load_gov_token_balance(deps.as_ref(), &info.sender, Some(poll.start_height - 1))?;
+    let snapshot_height = poll.start_height - 1;
+    let gov_token_balance_at_height = load_gov_token_balance(deps.as_ref(), &info.sender, Some(snapshot_height))?;
+    let delegated_power = load_delegated_power(deps.as_ref(), &info.sender, Some(snapshot_height), Some(&poll))?;
     let mut staker_state = StakerState::load_safe(deps.storage, &info.sender)?;
+    staker_state.check_vote_limit(deps.storage, poll_id)?;
 
-    if gov_token_balance_at_height < amount {
+    let amount = poll.get_vote_weight(&votes);
+    if gov_token_balance_at_height + delegated_power < amount {
         return Err(ContractError::Std(StdError::generic_err("User does not have enough staked tokens.")));
     }
 
     // Execute
     let mut response = make_response("cast_vote");
 
-    poll.vote(deps.storage, &mut staker_state, option.clone(), amount)?;
+    let prev_vote = poll.vote(deps.storage, &mut staker_state, votes, gov_token_balance_at_height)?;
+    let vote = poll.load_voter(deps.storage, &info.sender)?;
+
+    if let Some(delegate) = override_delegate_vote(deps.branch(), &mut poll, &info.sender)? {
+        response = response.add_attribute("overridden_delegate", delegate.as_str());
+    }
 
     poll.save(deps.storage)?;
     staker_state.save(deps.storage)?;
@@ -216,25 +528,113 @@ pub fn cast_vote(
     response = response.add_attribute("poll_id", &poll_id.to_string());
     response = response.add_attribute("amount", &amount.to_string());
     response = response.add_attribute("voter", info.sender.as_str());
-    response = response.add_attribute("voter_option", option.to_string());
+    response = response.add_attribute("voter_option", vote.options_to_string());
+
+    if let Some(prev_vote) = prev_vote {
+        response = response.add_attribute("prev_amount", prev_vote.amount.to_string());
+        response = response.add_attribute("prev_voter_option", prev_vote.options_to_string());
+    }
 
     Ok(response)
 }
 
-pub fn end_poll(
+// A direct vote overrides the voter's delegate on the poll.
+// The delegate's vote is scaled down to the remaining delegated power.
+fn override_delegate_vote(
+    deps: DepsMut,
+    poll: &mut Poll,
+    voter: &Addr,
+) -> StdResult<Option<Addr>> {
+    let snapshot_height = poll.start_height - 1;
+
+    let delegate = match load_delegate(deps.storage, voter, Some(snapshot_height))? {
+        Some(delegate) => delegate,
+        None => return Ok(None),
+    };
+
+    let delegate_vote = match poll.may_load_voter(deps.storage, &delegate)? {
+        Some(delegate_vote) => delegate_vote,
+        None => return Ok(None),
+    };
+
+    let delegated_power = load_delegated_power(deps.as_ref(), &delegate, Some(snapshot_height), Some(poll))?;
+    if delegate_vote.delegated_amount <= delegated_power {
+        return Ok(None);
+    }
+
+    let capped_amount = delegate_vote.amount - (delegate_vote.delegated_amount - delegated_power);
+    let votes = delegate_vote.weights().into_iter()
+        .map(|(option, amount)| (option, amount.multiply_ratio(capped_amount, delegate_vote.amount)))
+        .collect();
+
+    let mut delegate_state = StakerState::load_safe(deps.storage, &delegate)?;
+    poll.vote(deps.storage, &mut delegate_state, votes, delegate_vote.own_amount())?;
+    delegate_state.save(deps.storage)?;
+
+    Ok(Some(delegate))
+}
+
+pub fn retract_vote(
     deps: DepsMut,
     env: Env,
+    info: MessageInfo,
+    poll_id: u64,
+) -> ContractResult<Response> {
+    // Validate
+    let mut poll = Poll::may_load(deps.storage, &poll_id)?
+        .ok_or(ContractError::Std(StdError::generic_err("Poll does not exist")))?;
+
+    if !poll.in_progress(env.block.height) {
+        return Err(ContractError::Std(StdError::generic_err("Poll is not in progress")));
+    }
+
+    if PollConfig::load(deps.storage)?.early_finalization {
+        return Err(ContractError::Std(StdError::generic_err("Votes can not be changed while early finalization is enabled")));
+    }
+
+    // Execute
+    let mut response = make_response("retract_vote");
+
+    let mut staker_state = StakerState::load_safe(deps.storage, &info.sender)?;
+
+    let prev_vote = poll.retract_vote(deps.storage, &mut staker_state)?
+        .ok_or(ContractError::Std(StdError::generic_err("User has not voted.")))?;
+
+    poll.save(deps.storage)?;
+    staker_state.save(deps.storage)?;
+
+    response = response.add_attribute("poll_id", poll_id.to_string());
+    response = response.add_attribute("voter", info.sender.as_str());
+    response = response.add_attribute("prev_amount", prev_vote.amount.to_string());
+    response = response.add_attribute("prev_voter_option", prev_vote.options_to_string());
+
+    Ok(response)
+}
+
+pub fn end_poll(
+    mut deps: DepsMut,
+    env: Env,
     _info: MessageInfo,
     poll_id: u64,
 ) -> ContractResult<Response> {
     // Validate
     let mut poll = Poll::load(deps.storage, &poll_id)?;
 
+    if poll.status == PollStatus::DepositPeriod {
+        if poll.deposit_end_height >= env.block.height {
+            return Err(ContractError::Std(StdError::generic_err("Deposit period has not expired")));
+        }
+
+        return drop_poll(deps, env, poll);
+    }
+
     if poll.status != PollStatus::InProgress {
         return Err(ContractError::Std(StdError::generic_err("Poll is not in progress")));
     }
 
-    if poll.end_height > env.block.height {
+    let tally = poll.tally(deps.as_ref(), env.block.height)?;
+
+    if !tally.can_end {
         return Err(ContractError::Std(StdError::generic_err("Voting period has not expired")));
     }
 
@@ -244,7 +644,25 @@ pub fn end_poll(
     let contract_config = ContractConfig::load(deps.storage)?;
     let mut poll_state = PollState::load(deps.storage)?;
 
-    let (poll_result, gov_token_total_supply) = poll.get_result(deps.as_ref())?;
+    let poll_result = tally.result;
+    let gov_token_total_supply = tally.total_balance;
+    poll.finalized_early = tally.finalized_early;
+
+    // An expedited poll that misses the stricter requirements continues as a normal poll
+    if tally.expedited_fallback {
+        let poll_config = PollConfig::load(deps.storage)?;
+
+        poll.expedited = false;
+        poll.end_height = poll.start_height + poll.voting_period(&poll_config);
+        poll.save(deps.storage)?;
+
+        response = response.add_attribute("poll_id", poll_id.to_string());
+        response = response.add_attribute("result", poll_result.to_string());
+        response = response.add_attribute("expedited_fallback", "true");
+        response = response.add_attribute("end_height", poll.end_height.to_string());
+
+        return Ok(response);
+    }
 
     poll.status = if poll_result == PollResult::Passed {
         PollStatus::Passed
@@ -252,15 +670,53 @@ pub fn end_poll(
         PollStatus::Rejected
     };
 
-    // Refunds deposit only when quorum is reached
-    if poll_result != PollResult::QuorumNotReached && !poll.deposit_amount.is_zero() {
-        response = response.add_message(
-            message_factories::cw20_transfer(
-                &contract_config.governance_token,
-                &poll.creator,
-                poll.deposit_amount,
-            )
-        )
+    // The execution delay, expiry and guardian windows are measured from the end height
+    if poll.finalized_early {
+        poll.end_height = env.block.height;
+    }
+
+    if poll.is_multiple_choice() && poll_result == PollResult::Passed {
+        poll.winning_choice = poll.get_leading_choice();
+    }
+
+    // Refunds deposit only when quorum is reached and the poll is not vetoed.
+    // Vetoed deposit is sent to the veto deposit recipient or burned.
+    let mut burned_amount = Uint128::zero();
+    if !poll.deposit_amount.is_zero() {
+        match tally.deposit_outcome {
+            DepositOutcome::Confiscated => {
+                let poll_config = PollConfig::load(deps.storage)?;
+
+                response = response.add_message(confiscate_deposit(
+                    &poll_config,
+                    &contract_config.governance_token,
+                    poll.deposit_amount,
+                ));
+
+                if poll_config.veto_deposit_recipient.is_none() {
+                    burned_amount = poll.deposit_amount;
+                }
+            }
+            DepositOutcome::Refunded => {
+                let (refund_msgs, _) = refund_deposits(deps.storage, &poll, &contract_config.governance_token, Decimal::zero())?;
+                response = response.add_messages(refund_msgs);
+            }
+            DepositOutcome::Retained | DepositOutcome::Pending => {}
+        }
+    }
+
+    // Nobody can claim the voting reward of a poll without votes
+    let mut released_amount = Uint128::zero();
+    if poll.get_vote_amount().is_zero() {
+        released_amount = release_voting_reward(deps.storage, &mut poll)?;
+        if !released_amount.is_zero() {
+            response = response.add_attribute("released_voting_reward", released_amount.to_string());
+        }
+    }
+
+    // the released voting reward and the burned deposit move the exchange rate
+    if !released_amount.is_zero() || !burned_amount.is_zero() {
+        checkpoint_exchange_rate(deps.branch(), env.block.height, Uint128::zero(), burned_amount)?;
     }
 
     // Update poll status
@@ -275,91 +731,556 @@ pub fn end_poll(
     response = response.add_attribute("result", poll_result.to_string());
     response = response.add_attribute("passed", (poll_result == PollResult::Passed).to_string());
 
+    if poll.finalized_early {
+        response = response.add_attribute("finalized_early", "true");
+        response = response.add_attribute("end_height", poll.end_height.to_string());
+    }
+
+    if let Some(winning_choice) = poll.winning_choice {
+        response = response.add_attribute("winning_choice", winning_choice.to_string());
+    }
+
     Ok(response)
 }
 
-pub const REPLY_EXECUTION: u64 = 1;
-
-pub fn execute_poll(
-    deps: DepsMut,
+// Drops the poll which did not reach the proposal deposit and refunds all depositors
+fn drop_poll(
+    mut deps: DepsMut,
     env: Env,
-    _info: MessageInfo,
-    poll_id: u64,
+    mut poll: Poll,
 ) -> ContractResult<Response> {
-    // Validate
-    let poll_config = PollConfig::load(deps.storage)?;
-    let poll = Poll::load(deps.storage, &poll_id)?;
+    let mut response = make_response("end_poll");
 
-    if poll.status != PollStatus::Passed {
-        return Err(ContractError::Std(StdError::generic_err("Poll is not in passed status")));
-    }
+    let contract_config = ContractConfig::load(deps.storage)?;
 
-    if poll.end_height + poll_config.execution_delay_period > env.block.height {
-        return Err(ContractError::Std(StdError::generic_err("Execution delay period has not expired")));
-    }
+    let (refund_msgs, _) = refund_deposits(deps.storage, &poll, &contract_config.governance_token, Decimal::zero())?;
+    response = response.add_messages(refund_msgs);
 
-    let mut executions = poll.executions;
-    if executions.is_empty() {
-        return Err(ContractError::Std(StdError::generic_err("The poll does not have executions")));
+    if !release_voting_reward(deps.storage, &mut poll)?.is_zero() {
+        checkpoint_exchange_rate(deps.branch(), env.block.height, Uint128::zero(), Uint128::zero())?;
     }
 
-    // Execute
-    let mut response = make_response("execute_poll");
+    poll.status = PollStatus::Dropped;
+    poll.save_with_index(deps.storage)?;
 
-    executions.sort();
+    let mut poll_state = PollState::load(deps.storage)?;
+    poll_state.total_deposit = poll_state.total_deposit.checked_sub(poll.deposit_amount)?;
+    poll_state.save(deps.storage)?;
 
-    PollExecutionContext {
-        poll_id: poll.id,
-        execution_count: executions.len() as u64,
-    }.save(deps.storage)?;
+    response = response.add_attribute("poll_id", poll.id.to_string());
+    response = response.add_attribute("result", PollResult::DepositNotReached.to_string());
+    response = response.add_attribute("passed", "false");
 
-    response = response.add_submessage(SubMsg {
-        id: REPLY_EXECUTION,
-        msg: message_factories::wasm_execute(
-            &env.contract.address,
-            &ExecuteMsg::RunExecution {
-                executions: executions.iter().map(|e| ExecutionMsg::from(e)).collect(),
-            },
-        ),
-        gas_limit: None,
-        reply_on: ReplyOn::Always,
-    });
+    Ok(response)
+}
 
-    response = response.add_attribute("poll_id", poll_id.to_string());
+// Refunds deposits except the slashed ratio and returns the total refunded amount
+fn refund_deposits(
+    storage: &dyn Storage,
+    poll: &Poll,
+    governance_token: &Addr,
+    slash_rate: Decimal,
+) -> StdResult<(Vec<CosmosMsg>, Uint128)> {
+    let mut refund_msgs = vec![];
+    let mut refunded_amount = Uint128::zero();
+
+    for (depositor, amount) in poll.load_deposits(storage)?.iter() {
+        let refund_amount = amount.checked_sub(*amount * slash_rate)?;
+        if refund_amount.is_zero() {
+            continue;
+        }
 
-    Ok(response)
+        refund_msgs.push(message_factories::cw20_transfer(governance_token, depositor, refund_amount));
+        refunded_amount += refund_amount;
+    }
+
+    Ok((refund_msgs, refunded_amount))
 }
 
-pub fn run_execution(
-    deps: DepsMut,
+// Sends the deposit to the veto deposit recipient or burns it
+fn confiscate_deposit(
+    poll_config: &PollConfig,
+    governance_token: &Addr,
+    amount: Uint128,
+) -> CosmosMsg {
+    match poll_config.veto_deposit_recipient.as_ref() {
+        Some(recipient) => message_factories::cw20_transfer(
+            governance_token,
+            recipient,
+            amount,
+        ),
+        None => message_factories::wasm_execute(
+            governance_token,
+            &Cw20ExecuteMsg::Burn {
+                amount,
+            },
+        ),
+    }
+}
+
+pub fn cancel_poll(
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
-    mut executions: Vec<ExecutionMsg>,
+    poll_id: u64,
 ) -> ContractResult<Response> {
     // Validate
-    if env.contract.address != info.sender {
+    let mut poll = Poll::may_load(deps.storage, &poll_id)?
+        .ok_or(ContractError::Std(StdError::generic_err("Poll does not exist")))?;
+
+    if poll.creator != info.sender {
         return Err(ContractError::Unauthorized {});
     }
 
+    if !poll.in_progress(env.block.height) {
+        return Err(ContractError::Std(StdError::generic_err("Poll is not in progress")));
+    }
+
+    if !poll.get_vote_amount().is_zero() {
+        return Err(ContractError::Std(StdError::generic_err("Poll already has votes")));
+    }
+
     // Execute
-    let mut response = make_response("run_execution");
+    let mut response = make_response("cancel_poll");
 
-    executions.sort_by_key(|e| e.order);
+    let contract_config = ContractConfig::load(deps.storage)?;
+    let poll_config = PollConfig::load(deps.storage)?;
 
-    for execution in executions.iter() {
-        response = response.add_message(message_factories::wasm_execute_bin(
-            &deps.api.addr_validate(&execution.contract)?,
-            execution.msg.clone(),
+    let (refund_msgs, refunded_amount) = refund_deposits(
+        deps.storage,
+        &poll,
+        &contract_config.governance_token,
+        poll_config.cancel_slash_rate,
+    )?;
+    response = response.add_messages(refund_msgs);
+
+    let slashed_amount = poll.deposit_amount.checked_sub(refunded_amount)?;
+    let mut burned_amount = Uint128::zero();
+    if !slashed_amount.is_zero() {
+        response = response.add_message(confiscate_deposit(
+            &poll_config,
+            &contract_config.governance_token,
+            slashed_amount,
         ));
+
+        if poll_config.veto_deposit_recipient.is_none() {
+            burned_amount = slashed_amount;
+        }
     }
 
-    response = response.add_attribute("execution_count", executions.len().to_string());
+    // the released voting reward and the burned deposit move the exchange rate
+    let released_amount = release_voting_reward(deps.storage, &mut poll)?;
+    if !released_amount.is_zero() || !burned_amount.is_zero() {
+        checkpoint_exchange_rate(deps.branch(), env.block.height, Uint128::zero(), burned_amount)?;
+    }
 
-    Ok(response)
-}
+    poll.status = PollStatus::Cancelled;
+    poll.save_with_index(deps.storage)?;
 
-pub fn reply_execution(
-    deps: DepsMut,
+    let mut poll_state = PollState::load(deps.storage)?;
+    poll_state.total_deposit = poll_state.total_deposit.checked_sub(poll.deposit_amount)?;
+    poll_state.save(deps.storage)?;
+
+    response = response.add_attribute("poll_id", poll_id.to_string());
+    response = response.add_attribute("refunded_amount", refunded_amount.to_string());
+    response = response.add_attribute("slashed_amount", slashed_amount.to_string());
+
+    Ok(response)
+}
+
+pub fn fund_voting_reward(
+    deps: DepsMut,
+    _env: Env,
+    info: MessageInfo,
+    sender: Addr,
+    amount: Uint128,
+    poll_id: Option<u64>,
+) -> ContractResult<Response> {
+    // Validate
+    let contract_config = ContractConfig::load(deps.storage)?;
+    if !contract_config.is_staking_token(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if amount.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err("Insufficient funds sent")));
+    }
+
+    // Execute
+    let mut response = make_response("fund_voting_reward");
+
+    if let Some(poll_id) = poll_id {
+        add_voting_reward(deps.storage, poll_id, amount)?;
+
+        response = response.add_attribute("poll_id", poll_id.to_string());
+    } else {
+        // kept aside until governance allocates it to a poll
+        let mut staking_state = StakingState::load(deps.storage)?;
+        staking_state.unallocated_voting_reward += amount;
+        staking_state.save(deps.storage)?;
+    }
+
+    response = response.add_attribute("sender", sender.as_str());
+    response = response.add_attribute("amount", amount.to_string());
+
+    Ok(response)
+}
+
+// Allocates funded voting rewards not allocated yet, e.g. sent from the community pool, to a poll
+pub fn allocate_voting_reward(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    poll_id: u64,
+    amount: Uint128,
+) -> ContractResult<Response> {
+    // Validate
+    if env.contract.address != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if amount.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err("Invalid zero amount")));
+    }
+
+    let mut staking_state = StakingState::load(deps.storage)?;
+    if staking_state.unallocated_voting_reward < amount {
+        return Err(ContractError::Std(StdError::generic_err("Insufficient unallocated voting reward")));
+    }
+
+    // Execute
+    let mut response = make_response("allocate_voting_reward");
+
+    staking_state.unallocated_voting_reward = staking_state.unallocated_voting_reward.checked_sub(amount)?;
+    staking_state.save(deps.storage)?;
+
+    add_voting_reward(deps.storage, poll_id, amount)?;
+
+    response = response.add_attribute("poll_id", poll_id.to_string());
+    response = response.add_attribute("amount", amount.to_string());
+
+    Ok(response)
+}
+
+pub fn claim_voting_rewards(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    poll_ids: Vec<u64>,
+) -> ContractResult<Response> {
+    // Validate
+    let mut staker_state = StakerState::load_safe(deps.storage, &info.sender)?;
+    staker_state.clean_votes(deps.storage);
+
+    let voting_rewards = staker_state.take_voting_rewards(&poll_ids);
+    let amount = voting_rewards.iter().map(|(_, reward)| *reward).sum::<Uint128>();
+    if amount.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err("No voting rewards to claim")));
+    }
+
+    // Execute
+    let mut response = make_response("claim_voting_rewards");
+
+    staker_state.save(deps.storage)?;
+
+    // rounding dust no voter can claim anymore goes back to the staking pool
+    let mut swept_amount = Uint128::zero();
+    for (poll_id, reward) in voting_rewards {
+        let mut poll = Poll::load(deps.storage, &poll_id)?;
+        let vote_amount = Poll::may_load_staker_vote(deps.storage, &info.sender, poll_id)?
+            .map(|vote| vote.amount)
+            .unwrap_or_default();
+
+        swept_amount += poll.settle_voting_reward(reward, vote_amount);
+        poll.save(deps.storage)?;
+    }
+
+    let mut staking_state = StakingState::load(deps.storage)?;
+    staking_state.total_voting_reward = staking_state.total_voting_reward.checked_sub(amount + swept_amount)?;
+    staking_state.save(deps.storage)?;
+
+    // the swept dust raises the exchange rate, the claimed amount is paid out below
+    if !swept_amount.is_zero() {
+        checkpoint_exchange_rate(deps.branch(), env.block.height, amount, Uint128::zero())?;
+    }
+
+    let contract_config = ContractConfig::load(deps.storage)?;
+    response = response.add_message(message_factories::cw20_transfer(
+        &contract_config.staking_token,
+        &info.sender,
+        amount,
+    ));
+
+    response = response.add_attribute("staker", info.sender.as_str());
+    response = response.add_attribute("amount", amount.to_string());
+    response = response.add_attribute("swept_amount", swept_amount.to_string());
+
+    Ok(response)
+}
+
+fn add_voting_reward(storage: &mut dyn Storage, poll_id: u64, amount: Uint128) -> StdResult<()> {
+    let mut poll = Poll::may_load(storage, &poll_id)?
+        .ok_or_else(|| StdError::generic_err("Poll does not exist"))?;
+
+    if poll.status != PollStatus::DepositPeriod && poll.status != PollStatus::InProgress {
+        return Err(StdError::generic_err("Poll is not in progress"));
+    }
+
+    poll.voting_reward += amount;
+    poll.save(storage)?;
+
+    let mut staking_state = StakingState::load(storage)?;
+    staking_state.total_voting_reward += amount;
+    staking_state.save(storage)
+}
+
+// Returns the voting reward of the poll to the staking pool
+fn release_voting_reward(storage: &mut dyn Storage, poll: &mut Poll) -> StdResult<Uint128> {
+    let amount = poll.voting_reward;
+
+    if !amount.is_zero() {
+        let mut staking_state = StakingState::load(storage)?;
+        staking_state.total_voting_reward = staking_state.total_voting_reward.checked_sub(amount)?;
+        staking_state.save(storage)?;
+
+        poll.voting_reward = Uint128::zero();
+    }
+
+    Ok(amount)
+}
+
+pub const REPLY_EXECUTION: u64 = 1;
+// Reply ids of single executions are offset by their order
+pub const REPLY_EXECUTION_RESULT: u64 = 1 << 32;
+const MAX_EXECUTION_ORDER: u64 = u32::MAX as u64;
+
+pub fn execute_poll(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    poll_id: u64,
+) -> ContractResult<Response> {
+    // Validate
+    let poll_config = PollConfig::load(deps.storage)?;
+    let mut poll = Poll::load(deps.storage, &poll_id)?;
+
+    if poll.status != PollStatus::Passed {
+        return Err(ContractError::Std(StdError::generic_err("Poll is not in passed status")));
+    }
+
+    if poll.end_height + poll_config.execution_delay_period > env.block.height {
+        return Err(ContractError::Std(StdError::generic_err("Execution delay period has not expired")));
+    }
+
+    if poll.execution_expiry_height(&poll_config) < env.block.height {
+        return Err(ContractError::Std(StdError::generic_err("Execution period has expired")));
+    }
+
+    let mut executions = poll.executions.clone();
+    if executions.is_empty() {
+        return Err(ContractError::Std(StdError::generic_err("The poll does not have executions")));
+    }
+
+    // Execute
+    let mut response = make_response("execute_poll");
+
+    executions.sort();
+
+    PollExecutionContext {
+        poll_id: poll.id,
+        execution_count: executions.len() as u64,
+    }.save(deps.storage)?;
+
+    if poll.atomic {
+        // Runs every execution in a single sub message, so that one failure reverts all of them
+        response = response.add_submessage(SubMsg {
+            id: REPLY_EXECUTION,
+            msg: message_factories::wasm_execute(
+                &env.contract.address,
+                &ExecuteMsg::RunExecution {
+                    executions: executions.iter().map(ExecutionMsg::from).collect(),
+                },
+            ),
+            gas_limit: None,
+            reply_on: ReplyOn::Always,
+        });
+    } else {
+        let mut dispatched_count = 0;
+
+        for execution in executions.iter() {
+            // Executions denied by the current policy are recorded as failed without being dispatched
+            if let Err(err) = poll_config.execution_policy.validate(execution) {
+                poll.save_execution_result(deps.storage, &ExecutionResult {
+                    order: execution.order(),
+                    success: false,
+                    error: Some(err.to_string()),
+                })?;
+                continue;
+            }
+
+            response = response.add_submessage(SubMsg {
+                id: execution_reply_id(execution.order())?,
+                msg: execution.to_cosmos_msg(),
+                gas_limit: None,
+                reply_on: ReplyOn::Always,
+            });
+            dispatched_count += 1;
+        }
+
+        if dispatched_count == 0 {
+            poll.status = PollStatus::Failed;
+            poll.save_with_index(deps.storage)?;
+            PollExecutionContext::clear(deps.storage);
+
+            response = response.add_attribute("poll_status", poll.status.to_string());
+        }
+    }
+
+    response = response.add_attribute("poll_id", poll_id.to_string());
+    response = response.add_attribute("atomic", poll.atomic.to_string());
+
+    Ok(response)
+}
+
+pub fn expire_poll(
+    deps: DepsMut,
+    env: Env,
+    _info: MessageInfo,
+    poll_id: u64,
+) -> ContractResult<Response> {
+    // Validate
+    let poll_config = PollConfig::load(deps.storage)?;
+    let mut poll = Poll::load(deps.storage, &poll_id)?;
+
+    if poll.status != PollStatus::Passed {
+        return Err(ContractError::Std(StdError::generic_err("Poll is not in passed status")));
+    }
+
+    if poll.execution_expiry_height(&poll_config) >= env.block.height {
+        return Err(ContractError::Std(StdError::generic_err("Execution period has not expired")));
+    }
+
+    // Execute
+    let mut response = make_response("expire_poll");
+
+    poll.status = PollStatus::Expired;
+    poll.save_with_index(deps.storage)?;
+
+    response = response.add_attribute("poll_id", poll_id.to_string());
+
+    Ok(response)
+}
+
+pub fn veto_poll(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    poll_id: u64,
+) -> ContractResult<Response> {
+    // Validate
+    let poll_config = PollConfig::load(deps.storage)?;
+    let mut poll = Poll::load(deps.storage, &poll_id)?;
+
+    let guardian = poll_config.guardian
+        .filter(|guardian| guardian.address == info.sender)
+        .ok_or(ContractError::Unauthorized {})?;
+
+    if !guardian.is_active(env.block.height) {
+        return Err(ContractError::Std(StdError::generic_err("Guardian power has expired")));
+    }
+
+    if poll.status != PollStatus::Passed {
+        return Err(ContractError::Std(StdError::generic_err("Poll is not in passed status")));
+    }
+
+    if poll.end_height + poll_config.execution_delay_period <= env.block.height {
+        return Err(ContractError::Std(StdError::generic_err("Execution delay period has expired")));
+    }
+
+    // Execute
+    let mut response = make_response("veto_poll");
+
+    poll.status = PollStatus::Vetoed;
+    poll.save_with_index(deps.storage)?;
+
+    response = response.add_attribute("poll_id", poll_id.to_string());
+    response = response.add_attribute("guardian", guardian.address.as_str());
+
+    Ok(response)
+}
+
+pub fn update_guardian(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    guardian: Option<GuardianMsg>,
+) -> ContractResult<Response> {
+    // Validate
+    if env.contract.address != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let guardian = guardian
+        .map(|g| Guardian::from_msg(deps.api, &g, env.block.height))
+        .transpose()?;
+
+    // Execute
+    let mut response = make_response("update_guardian");
+
+    let mut poll_config = PollConfig::load(deps.storage)?;
+    poll_config.guardian = guardian;
+    poll_config.save(deps.storage)?;
+
+    match poll_config.guardian.as_ref() {
+        Some(guardian) => {
+            response = response.add_attribute("guardian", guardian.address.as_str());
+            response = response.add_attribute("expiry_height", guardian.expiry_height.to_string());
+        }
+        None => {
+            response = response.add_attribute("guardian", "none");
+        }
+    }
+
+    Ok(response)
+}
+
+pub fn run_execution(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    mut executions: Vec<ExecutionMsg>,
+) -> ContractResult<Response> {
+    // Validate
+    if env.contract.address != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // Execute
+    let mut response = make_response("run_execution");
+
+    let poll_config = PollConfig::load(deps.storage)?;
+
+    executions.sort_by_key(|e| e.order());
+
+    for execution in executions.iter() {
+        let execution = Execution::from(deps.api, execution)?;
+        poll_config.execution_policy.validate(&execution)?;
+
+        response = response.add_submessage(SubMsg {
+            id: execution_reply_id(execution.order())?,
+            msg: execution.to_cosmos_msg(),
+            gas_limit: None,
+            reply_on: ReplyOn::Always,
+        });
+    }
+
+    response = response.add_attribute("execution_count", executions.len().to_string());
+
+    Ok(response)
+}
+
+pub fn reply_execution(
+    deps: DepsMut,
     _env: Env,
     msg: Reply,
 ) -> ContractResult<Response> {
@@ -367,17 +1288,27 @@ pub fn reply_execution(
     let poll_execution_context = PollExecutionContext::load(deps.storage)?;
     let mut poll = Poll::load(deps.storage, &poll_execution_context.poll_id)?;
 
-    if poll.status == PollStatus::Failed || poll.status == PollStatus::Executed {
+    if poll.is_executed() {
         return Err(ContractError::Std(StdError::generic_err("Already executed")));
     }
 
     // Execute
     let mut response = make_response("reply_execution");
 
-    poll.status = if msg.result.is_ok() {
-        PollStatus::Executed
-    } else {
-        PollStatus::Failed
+    poll.status = match msg.result {
+        SubMsgResult::Ok(_) => PollStatus::Executed,
+        SubMsgResult::Err(err) => {
+            // Every execution has been reverted
+            for execution in poll.executions.iter() {
+                poll.save_execution_result(deps.storage, &ExecutionResult {
+                    order: execution.order(),
+                    success: false,
+                    error: Some(err.clone()),
+                })?;
+            }
+
+            PollStatus::Failed
+        }
     };
 
     poll.save_with_index(deps.storage)?;
@@ -388,6 +1319,84 @@ pub fn reply_execution(
     Ok(response)
 }
 
+pub fn reply_execution_result(
+    deps: DepsMut,
+    _env: Env,
+    msg: Reply,
+) -> ContractResult<Response> {
+    // Validate
+    let poll_execution_context = PollExecutionContext::load(deps.storage)?;
+    let mut poll = Poll::load(deps.storage, &poll_execution_context.poll_id)?;
+
+    if poll.is_executed() {
+        return Err(ContractError::Std(StdError::generic_err("Already executed")));
+    }
+
+    let order = msg.id - REPLY_EXECUTION_RESULT;
+
+    // Execute
+    let mut response = make_response("reply_execution_result");
+
+    let result = match msg.result {
+        SubMsgResult::Ok(_) => ExecutionResult {
+            order,
+            success: true,
+            error: None,
+        },
+        SubMsgResult::Err(err) => {
+            if poll.atomic {
+                return Err(ContractError::Std(StdError::generic_err(
+                    format!("Execution {} failed: {}", order, err),
+                )));
+            }
+
+            ExecutionResult {
+                order,
+                success: false,
+                error: Some(err),
+            }
+        }
+    };
+
+    poll.save_execution_result(deps.storage, &result)?;
+
+    response = response.add_attribute("poll_id", poll.id.to_string());
+    response = response.add_attribute("order", order.to_string());
+    response = response.add_attribute("success", result.success.to_string());
+
+    // Best-effort polls are settled once every execution has replied
+    if !poll.atomic {
+        let results = Poll::load_execution_results(deps.storage, &poll.id)?;
+
+        if results.len() as u64 >= poll_execution_context.execution_count {
+            let success_count = results.iter().filter(|r| r.success).count();
+
+            poll.status = if success_count == results.len() {
+                PollStatus::Executed
+            } else if success_count == 0 {
+                PollStatus::Failed
+            } else {
+                PollStatus::PartiallyExecuted
+            };
+
+            poll.save_with_index(deps.storage)?;
+            PollExecutionContext::clear(deps.storage);
+
+            response = response.add_attribute("poll_status", poll.status.to_string());
+        }
+    }
+
+    Ok(response)
+}
+
+fn execution_reply_id(order: u64) -> StdResult<u64> {
+    if order > MAX_EXECUTION_ORDER {
+        return Err(StdError::generic_err("Execution order too large"));
+    }
+
+    Ok(REPLY_EXECUTION_RESULT + order)
+}
+
 // Validate_quorum returns an error if the quorum is invalid
 /// (we require 0-1)
 fn validate_quorum(quorum: Decimal) -> StdResult<()> {
@@ -408,6 +1417,16 @@ fn validate_threshold(threshold: Decimal) -> StdResult<()> {
     }
 }
 
+// Validate_veto_threshold returns an error if the veto threshold is invalid
+/// (we require 0-1)
+fn validate_veto_threshold(veto_threshold: Decimal) -> StdResult<()> {
+    if veto_threshold > Decimal::one() {
+        Err(StdError::generic_err("veto_threshold must be 0 to 1"))
+    } else {
+        Ok(())
+    }
+}
+
 // Validate_threshold returns an error if the threshold is invalid
 /// (we require 1000+)
 fn validate_execution_delay_period(execution_delay_period: u64) -> StdResult<()> {
@@ -418,6 +1437,28 @@ fn validate_execution_delay_period(execution_delay_period: u64) -> StdResult<()>
     }
 }
 
+// Validate_expedited returns an error if the expedited settings are not stricter than the normal ones
+// (we require 0 < expedited_voting_period < voting_period, expedited_threshold >= threshold
+// and expedited_proposal_deposit >= proposal_deposit)
+fn validate_expedited(
+    expedited_voting_period: u64,
+    expedited_threshold: Decimal,
+    expedited_proposal_deposit: Uint128,
+    voting_period: u64,
+    threshold: Decimal,
+    proposal_deposit: Uint128,
+) -> StdResult<()> {
+    if expedited_voting_period == 0 || expedited_voting_period >= voting_period {
+        Err(StdError::generic_err("expedited_voting_period must be greater than 0 and less than voting_period"))
+    } else if expedited_threshold < threshold {
+        Err(StdError::generic_err("expedited_threshold must not be less than threshold"))
+    } else if expedited_proposal_deposit < proposal_deposit {
+        Err(StdError::generic_err("expedited_proposal_deposit must not be less than proposal_deposit"))
+    } else {
+        Ok(())
+    }
+}
+
 // Validate_title returns an error if the title is invalid
 fn validate_title(title: &str) -> StdResult<()> {
     if title.len() < MIN_TITLE_LENGTH {
@@ -455,21 +1496,100 @@ fn validate_link(link: &Option<String>) -> StdResult<()> {
     }
 }
 
-fn validate_executions(executions: &Vec<ExecutionMsg>) -> StdResult<()> {
-    for execution in executions.iter() {
-        match from_binary(&execution.msg) {
-            Ok(Cw20ExecuteMsg::Transfer { amount: _, recipient: _ }) => {
-                return Err(StdError::generic_err("Can't use Transfer message"))
-            },
-            Ok(Cw20ExecuteMsg::Send { amount: _, contract: _, msg: _ }) => {
-                return Err(StdError::generic_err("Can't use Send message"))
-            },
-            Ok(Cw20ExecuteMsg::IncreaseAllowance { spender: _, amount: _, expires: _}) => {
-                return Err(StdError::generic_err("Can't use IncreaseAllowance message"))
+// Validate_votes returns an error if the vote options are empty or duplicated
+fn validate_votes(votes: &[(VoteOption, Uint128)]) -> StdResult<()> {
+    if votes.is_empty() {
+        return Err(StdError::generic_err("Must vote at least one option"));
+    }
+
+    for (index, (option, amount)) in votes.iter().enumerate() {
+        if amount.is_zero() {
+            return Err(StdError::generic_err(format!("Vote amount must be greater than zero: {}", option)));
+        }
+
+        if votes[..index].iter().any(|(prev_option, _)| prev_option == option) {
+            return Err(StdError::generic_err(format!("Duplicated vote option: {}", option)));
+        }
+    }
+
+    Ok(())
+}
+
+// Validate_poll_kind returns an error if the options of a multiple choice poll are invalid
+fn validate_poll_kind(kind: &PollKind, executions: &[ExecutionMsg]) -> StdResult<()> {
+    let options = match kind {
+        PollKind::Binary {} => return Ok(()),
+        PollKind::MultipleChoice { options, .. } => options,
+    };
+
+    if !executions.is_empty() {
+        return Err(StdError::generic_err("Multiple choice poll can not have executions"));
+    }
+
+    if options.len() < MIN_POLL_OPTIONS || options.len() > MAX_POLL_OPTIONS {
+        return Err(StdError::generic_err(
+            format!("Poll must have {} to {} options", MIN_POLL_OPTIONS, MAX_POLL_OPTIONS),
+        ));
+    }
+
+    for (index, option) in options.iter().enumerate() {
+        if option.is_empty() || option.len() > MAX_POLL_OPTION_LENGTH {
+            return Err(StdError::generic_err("Invalid poll option length"));
+        }
+
+        if options[..index].contains(option) {
+            return Err(StdError::generic_err(format!("Duplicated poll option: {}", option)));
+        }
+    }
+
+    Ok(())
+}
+
+// Validate_vote_options returns an error if the vote options do not fit the poll kind
+fn validate_vote_options(kind: &PollKind, votes: &[(VoteOption, Uint128)]) -> StdResult<()> {
+    match kind {
+        PollKind::Binary {} => {
+            if let Some((option, _)) = votes.iter().find(|(option, _)| matches!(option, VoteOption::Choice(_))) {
+                return Err(StdError::generic_err(format!("Invalid vote option: {}", option)));
             }
-            _ => continue
         }
+        PollKind::MultipleChoice { options, rule } => {
+            for (option, _) in votes.iter() {
+                match option {
+                    VoteOption::Choice(index) if (*index as usize) < options.len() => {}
+                    _ => return Err(StdError::generic_err(format!("Invalid vote option: {}", option))),
+                }
+            }
+
+            match rule {
+                TallyRule::Plurality => if votes.len() > 1 {
+                    return Err(StdError::generic_err("Plurality poll allows a single option"));
+                },
+                TallyRule::Approval => if votes.iter().any(|(_, amount)| *amount != votes[0].1) {
+                    return Err(StdError::generic_err("Approved options must have the same amount"));
+                },
+            }
+        }
+    }
 
+    Ok(())
+}
+
+fn validate_executions(executions: &Vec<ExecutionMsg>) -> StdResult<()> {
+    for (index, execution) in executions.iter().enumerate() {
+        let order = execution.order();
+
+        if order > MAX_EXECUTION_ORDER {
+            return Err(StdError::generic_err("Execution order too large"));
+        }
+
+        if executions[..index].iter().any(|prev| prev.order() == order) {
+            return Err(StdError::generic_err(format!("Duplicated execution order: {}", order)));
+        }
+
+        if let ExecutionMsg::Execute { msg, .. } = execution {
+            parse_message_name(msg)?;
+        }
     }
 
     Ok(())
@@ -3,4 +3,4 @@ pub mod executions;
 pub mod queries;
 
 #[cfg(test)]
-mod tests;
\ No newline at end of file
+pub mod tests;
\ No newline at end of file
@@ -3,12 +3,12 @@ use cosmwasm_std::{Deps, Env, StdError};
 use terrapoker::common::{ContractResult, OrderBy};
 use terrapoker::errors::ContractError;
 use terrapoker::governance::enumerations::PollStatus;
-use terrapoker::governance::query_msgs::{PollConfigResponse, PollResponse, PollsResponse, PollStateResponse, VotersResponse};
+use terrapoker::governance::models::DepositInfoMsg;
+use terrapoker::governance::query_msgs::{PollConfigResponse, PollDepositsResponse, PollExecutionResultsResponse, PollResponse, PollTallyResponse, PollsResponse, PollStateResponse, VotersResponse};
 
-use crate::poll::states::Poll;
+use crate::poll::states::{Poll, PollResult};
 
 use super::states::{PollConfig, PollState};
-use terrapoker::governance::models::VoteInfoMsg;
 
 pub fn get_poll_config(
     deps: Deps,
@@ -23,6 +23,21 @@ pub fn get_poll_config(
             voting_period: poll_config.voting_period,
             execution_delay_period: poll_config.execution_delay_period,
             proposal_deposit: poll_config.proposal_deposit,
+            deposit_period: poll_config.deposit_period,
+            veto_threshold: poll_config.veto_threshold,
+            veto_deposit_recipient: poll_config.veto_deposit_recipient.map(|r| r.to_string()),
+            cancel_slash_rate: poll_config.cancel_slash_rate,
+            execution_expiry_period: poll_config.execution_expiry_period,
+            execution_policy: poll_config.execution_policy.to_msg(),
+            expedited_voting_period: poll_config.expedited_voting_period,
+            expedited_threshold: poll_config.expedited_threshold,
+            expedited_proposal_deposit: poll_config.expedited_proposal_deposit,
+            early_finalization: poll_config.early_finalization,
+            min_proposer_balance: poll_config.min_proposer_balance,
+            max_polls_per_proposer: poll_config.max_polls_per_proposer,
+            proposal_cooldown_period: poll_config.proposal_cooldown_period,
+            quorum_base: poll_config.quorum_base.to_msg(),
+            guardian: poll_config.guardian.map(|g| g.to_msg()),
         }
     )
 }
@@ -71,6 +86,77 @@ pub fn query_polls(
     )
 }
 
+pub fn query_poll_deposits(
+    deps: Deps,
+    _env: Env,
+    poll_id: u64,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> ContractResult<PollDepositsResponse> {
+    let start_after = start_after.map(|s| deps.api.addr_validate(&s)).transpose()?;
+
+    let deposits = Poll::read_deposits(deps.storage, &poll_id, start_after, limit)?.iter()
+        .map(|(depositor, amount)| DepositInfoMsg {
+            depositor: depositor.to_string(),
+            amount: *amount,
+        })
+        .collect();
+
+    Ok(
+        PollDepositsResponse {
+            deposits,
+        }
+    )
+}
+
+pub fn query_poll_execution_results(
+    deps: Deps,
+    _env: Env,
+    poll_id: u64,
+) -> ContractResult<PollExecutionResultsResponse> {
+    Poll::load(deps.storage, &poll_id)?;
+
+    let results = Poll::load_execution_results(deps.storage, &poll_id)?.iter()
+        .map(|result| result.to_msg())
+        .collect();
+
+    Ok(
+        PollExecutionResultsResponse {
+            results,
+        }
+    )
+}
+
+pub fn query_poll_tally(
+    deps: Deps,
+    env: Env,
+    poll_id: u64,
+) -> ContractResult<PollTallyResponse> {
+    let poll = Poll::may_load(deps.storage, &poll_id)?
+        .ok_or(ContractError::Std(StdError::generic_err("Poll does not exist")))?;
+
+    if poll.status != PollStatus::InProgress {
+        return Err(ContractError::Std(StdError::generic_err("Poll is not in progress")));
+    }
+
+    let tally = poll.tally(deps, env.block.height)?;
+
+    Ok(
+        PollTallyResponse {
+            poll_id,
+            quorum: tally.quorum,
+            yes_ratio: tally.yes_ratio,
+            result: tally.result.to_string(),
+            passed: tally.result == PollResult::Passed,
+            blocks_remaining: poll.end_height.saturating_sub(env.block.height),
+            can_end: tally.can_end,
+            finalized_early: tally.finalized_early,
+            expedited_fallback: tally.expedited_fallback,
+            deposit_outcome: tally.deposit_outcome,
+        }
+    )
+}
+
 pub fn query_voters(
     deps: Deps,
     _env: Env,
@@ -82,19 +168,9 @@ pub fn query_voters(
     let poll = Poll::may_load(deps.storage, &poll_id)?
         .ok_or(ContractError::Std(StdError::generic_err("Poll does not exist")))?;
 
-    let voters = if poll.status != PollStatus::InProgress {
-        vec![]
-    } else {
-        Poll::read_voters(deps.storage, &poll_id, start_after, limit, order_by)?
-    };
-
-    let response_items = voters.iter().map(|(voter, voter_info)| {
-        VoteInfoMsg {
-            voter: voter.to_string(),
-            option: voter_info.option.clone(),
-            amount: voter_info.amount,
-        }
-    }).collect();
+    let voters = Poll::read_voters(deps.storage, &poll.id, start_after, limit, order_by)?;
+
+    let response_items = voters.iter().map(|(_, voter_info)| voter_info.to_msg()).collect();
 
     Ok(
         VotersResponse {
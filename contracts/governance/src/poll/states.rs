@@ -1,19 +1,24 @@
 use std::fmt;
 
-use cosmwasm_std::{Addr, Decimal, Deps, StdResult, Storage, Uint128};
+use cosmwasm_std::{Addr, Api, Decimal, Deps, Order, StdError, StdResult, Storage, Uint128};
 use cw_storage_plus::{Bound, Item, Map};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use terrapoker::common::{OrderBy, Execution, ExecutionMsg};
-use terrapoker::governance::enumerations::{PollStatus, VoteOption};
+use terrapoker::governance::actions::GovActionMsg;
+use terrapoker::governance::enumerations::{DepositOutcome, PollKind, PollStatus, TallyRule, VoteOption};
+use terrapoker::governance::models::{ExecutionPolicyMsg, ExecutionResultMsg, ExecutionTargetPolicyMsg, GuardianMsg, QuorumBaseMsg, VoteInfoMsg};
+use terrapoker::utils::validate_zero_to_one;
 use terrapoker::governance::query_msgs::PollResponse;
-use crate::common::states::{ContractConfig, load_gov_token_total_supply};
+use crate::common::states::{load_gov_token_balance, load_gov_token_total_supply};
 
 use crate::staking::states::{StakerState};
 
 const MAX_LIMIT: u32 = 30;
 const DEFAULT_LIMIT: u32 = 10;
+pub const MAX_DEPOSITORS: usize = 50;
+pub const MAX_QUORUM_EXCLUDED_ADDRESSES: usize = 30;
 
 
 const POLL_CONFIG: Item<PollConfig> = Item::new("poll-config");
@@ -25,6 +30,57 @@ pub struct PollConfig {
     pub voting_period: u64,
     pub execution_delay_period: u64,
     pub proposal_deposit: Uint128,
+    #[serde(default)]
+    pub deposit_period: u64,
+    #[serde(default = "default_veto_threshold")]
+    pub veto_threshold: Decimal,
+    #[serde(default)]
+    pub veto_deposit_recipient: Option<Addr>, // burns vetoed deposit if none
+    #[serde(default)]
+    pub cancel_slash_rate: Decimal, // ratio of the deposit not refunded on cancellation
+    #[serde(default = "default_execution_expiry_period")]
+    pub execution_expiry_period: u64, // blocks after the execution delay in which a passed poll can be executed
+    #[serde(default = "default_execution_policy")]
+    pub execution_policy: ExecutionPolicy,
+    #[serde(default)]
+    pub expedited_voting_period: u64, // 0 if expedited polls are not enabled
+    #[serde(default = "default_expedited_threshold")]
+    pub expedited_threshold: Decimal,
+    #[serde(default)]
+    pub expedited_proposal_deposit: Uint128, // deposit required to start voting on an expedited poll
+    #[serde(default)]
+    pub early_finalization: bool, // allows ending a poll before end_height once the result is decided
+    #[serde(default)]
+    pub min_proposer_balance: Uint128, // xTPT balance a proposer must hold at the previous block
+    #[serde(default)]
+    pub max_polls_per_proposer: u64, // open polls allowed per proposer, 0 for no limit
+    #[serde(default)]
+    pub proposal_cooldown_period: u64, // blocks between polls from the same proposer
+    #[serde(default)]
+    pub quorum_base: QuorumBase, // amount the quorum is measured against
+    #[serde(default)]
+    pub guardian: Option<Guardian>, // can veto passed polls during the execution delay until expiry_height
+}
+
+// Defaults keep the behaviour of configs stored before the fields were added.
+fn default_veto_threshold() -> Decimal {
+    Decimal::one()
+}
+
+fn default_execution_expiry_period() -> u64 {
+    u64::MAX
+}
+
+fn default_expedited_threshold() -> Decimal {
+    Decimal::one()
+}
+
+// Configs stored without a policy deny every execution until the migration seeds one.
+fn default_execution_policy() -> ExecutionPolicy {
+    ExecutionPolicy {
+        allow_any_target: false,
+        targets: vec![],
+    }
 }
 
 impl PollConfig {
@@ -38,6 +94,213 @@ impl PollConfig {
 }
 
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExecutionPolicy {
+    pub allow_any_target: bool, // targets not listed are allowed without restriction
+    pub targets: Vec<ExecutionTargetPolicy>,
+}
+
+// cw20 messages moving the staked pool or the deposits, always denied on the staking and governance tokens
+pub const TOKEN_TRANSFER_MESSAGES: [&str; 5] = ["transfer", "transfer_from", "send", "send_from", "increase_allowance"];
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExecutionTargetPolicy {
+    pub target: Addr,
+    pub denied_messages: Vec<String>,
+    pub threshold: Option<Decimal>, // overrides the poll threshold when set
+}
+
+impl ExecutionPolicy {
+    pub fn from_msg(api: &dyn Api, msg: &ExecutionPolicyMsg) -> StdResult<ExecutionPolicy> {
+        let mut targets: Vec<ExecutionTargetPolicy> = vec![];
+
+        for target in msg.targets.iter() {
+            let address = api.addr_validate(&target.target)?;
+            if targets.iter().any(|t| t.target == address) {
+                return Err(StdError::generic_err(format!("Duplicated execution target: {}", address)));
+            }
+
+            if let Some(threshold) = target.threshold {
+                validate_zero_to_one(threshold, "threshold")?;
+            }
+
+            targets.push(ExecutionTargetPolicy {
+                target: address,
+                denied_messages: target.denied_messages.clone(),
+                threshold: target.threshold,
+            });
+        }
+
+        Ok(ExecutionPolicy {
+            allow_any_target: msg.allow_any_target,
+            targets,
+        })
+    }
+
+    // adds the token transfer messages to the denied messages of the tokens
+    pub fn deny_token_transfers(&mut self, tokens: &[&Addr]) {
+        for token in tokens {
+            let policy = match self.targets.iter().position(|t| &t.target == *token) {
+                Some(index) => &mut self.targets[index],
+                None => {
+                    self.targets.push(ExecutionTargetPolicy {
+                        target: (*token).clone(),
+                        denied_messages: vec![],
+                        threshold: None,
+                    });
+                    self.targets.last_mut().unwrap()
+                }
+            };
+
+            for message in TOKEN_TRANSFER_MESSAGES {
+                if !policy.denied_messages.iter().any(|m| m == message) {
+                    policy.denied_messages.push(message.to_string());
+                }
+            }
+        }
+    }
+
+    pub fn to_msg(&self) -> ExecutionPolicyMsg {
+        ExecutionPolicyMsg {
+            allow_any_target: self.allow_any_target,
+            targets: self.targets.iter().map(|t| ExecutionTargetPolicyMsg {
+                target: t.target.to_string(),
+                denied_messages: t.denied_messages.clone(),
+                threshold: t.threshold,
+            }).collect(),
+        }
+    }
+
+    pub fn validate(&self, execution: &Execution) -> StdResult<()> {
+        let target = execution.target();
+
+        match self.targets.iter().find(|t| &t.target == target) {
+            Some(policy) => {
+                let message_name = execution.message_name()?;
+                if policy.denied_messages.contains(&message_name) {
+                    return Err(StdError::generic_err(
+                        format!("Execution message not allowed: {} on {}", message_name, target),
+                    ));
+                }
+
+                Ok(())
+            }
+            None if self.allow_any_target => Ok(()),
+            None => Err(StdError::generic_err(format!("Execution target not allowed: {}", target))),
+        }
+    }
+
+    // Highest threshold among the targets of the executions that override it
+    pub fn threshold(&self, executions: &[Execution]) -> Option<Decimal> {
+        executions.iter()
+            .filter_map(|e| self.targets.iter().find(|t| &t.target == e.target()))
+            .filter_map(|t| t.threshold)
+            .max()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QuorumBase {
+    TotalSupply {},
+    ExcludingAddresses { addresses: Vec<Addr> },
+    Fixed { amount: Uint128 },
+}
+
+impl Default for QuorumBase {
+    fn default() -> Self {
+        QuorumBase::TotalSupply {}
+    }
+}
+
+impl QuorumBase {
+    pub fn from_msg(api: &dyn Api, msg: &QuorumBaseMsg) -> StdResult<QuorumBase> {
+        match msg {
+            QuorumBaseMsg::TotalSupply {} => Ok(QuorumBase::TotalSupply {}),
+            QuorumBaseMsg::ExcludingAddresses { addresses } => {
+                if addresses.len() > MAX_QUORUM_EXCLUDED_ADDRESSES {
+                    return Err(StdError::generic_err("Too many excluded addresses"));
+                }
+
+                let mut validated: Vec<Addr> = vec![];
+                for address in addresses.iter() {
+                    let address = api.addr_validate(address)?;
+                    if validated.contains(&address) {
+                        return Err(StdError::generic_err(format!("Duplicated excluded address: {}", address)));
+                    }
+
+                    validated.push(address);
+                }
+
+                Ok(QuorumBase::ExcludingAddresses { addresses: validated })
+            }
+            QuorumBaseMsg::Fixed { amount } => {
+                if amount.is_zero() {
+                    return Err(StdError::generic_err("Fixed quorum base must be greater than 0"));
+                }
+
+                Ok(QuorumBase::Fixed { amount: *amount })
+            }
+        }
+    }
+
+    pub fn to_msg(&self) -> QuorumBaseMsg {
+        match self {
+            QuorumBase::TotalSupply {} => QuorumBaseMsg::TotalSupply {},
+            QuorumBase::ExcludingAddresses { addresses } => QuorumBaseMsg::ExcludingAddresses {
+                addresses: addresses.iter().map(|a| a.to_string()).collect(),
+            },
+            QuorumBase::Fixed { amount } => QuorumBaseMsg::Fixed { amount: *amount },
+        }
+    }
+
+    pub fn load_amount(&self, deps: Deps, height: u64) -> StdResult<Uint128> {
+        match self {
+            QuorumBase::TotalSupply {} => load_gov_token_total_supply(deps, Some(height)),
+            QuorumBase::ExcludingAddresses { addresses } => {
+                let mut amount = load_gov_token_total_supply(deps, Some(height))?;
+                for address in addresses.iter() {
+                    amount = amount.saturating_sub(load_gov_token_balance(deps, address, Some(height))?);
+                }
+
+                Ok(amount)
+            }
+            QuorumBase::Fixed { amount } => Ok(*amount),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Guardian {
+    pub address: Addr,
+    pub expiry_height: u64,
+}
+
+impl Guardian {
+    pub fn from_msg(api: &dyn Api, msg: &GuardianMsg, block_height: u64) -> StdResult<Guardian> {
+        if msg.expiry_height <= block_height {
+            return Err(StdError::generic_err("Guardian expiry height must be in the future"));
+        }
+
+        Ok(Guardian {
+            address: api.addr_validate(&msg.address)?,
+            expiry_height: msg.expiry_height,
+        })
+    }
+
+    pub fn to_msg(&self) -> GuardianMsg {
+        GuardianMsg {
+            address: self.address.to_string(),
+            expiry_height: self.expiry_height,
+        }
+    }
+
+    pub fn is_active(&self, block_height: u64) -> bool {
+        block_height < self.expiry_height
+    }
+}
+
+
 const POLL_STATE: Item<PollState> = Item::new("poll-state");
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -70,7 +333,45 @@ pub fn get_poll_id(storage: &mut dyn Storage, deposit_amount: &Uint128) -> StdRe
 
 const POLLS: Map<&[u8], Poll> = Map::new("poll");
 const POLL_STATUS_INDEX: Map<(&[u8], &[u8]), bool> = Map::new("poll-status-index");
+const PROPOSER_STATES: Map<&Addr, ProposerState> = Map::new("proposer-state");
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ProposerState {
+    pub address: Addr,
+    pub open_polls: Vec<u64>, // polls in deposit period or in progress
+    pub last_poll_height: Option<u64>,
+}
+
+impl ProposerState {
+    pub fn default(address: &Addr) -> ProposerState {
+        ProposerState {
+            address: address.clone(),
+            open_polls: vec![],
+            last_poll_height: None,
+        }
+    }
+
+    pub fn save(&self, storage: &mut dyn Storage) -> StdResult<()> {
+        PROPOSER_STATES.save(storage, &self.address, self)
+    }
+
+    pub fn load_safe(storage: &dyn Storage, address: &Addr) -> StdResult<ProposerState> {
+        Ok(PROPOSER_STATES.may_load(storage, address)?.unwrap_or(ProposerState::default(address)))
+    }
+
+    pub fn clean_open_polls(&mut self, storage: &dyn Storage) {
+        self.open_polls.retain(|poll_id| {
+            Poll::load(storage, poll_id).ok()
+                .map(|p| p.status == PollStatus::DepositPeriod || p.status == PollStatus::InProgress)
+                .unwrap_or(false)
+        });
+    }
+}
+
 const POLL_VOTERS: Map<(&[u8], &[u8]), VoteInfo> = Map::new("poll-voter");
+const STAKER_VOTES: Map<(&Addr, u64), VoteInfo> = Map::new("staker-vote"); // voting history by staker and poll id
+const POLL_DEPOSITS: Map<(&[u8], &Addr), Uint128> = Map::new("poll-deposit");
+const POLL_EXECUTION_RESULTS: Map<(&[u8], &[u8]), ExecutionResult> = Map::new("poll-execution-result");
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Poll {
@@ -84,14 +385,48 @@ pub struct Poll {
     pub yes_votes: Uint128,
     pub no_votes: Uint128,
     pub abstain_votes: Uint128,
+    #[serde(default)]
+    pub no_with_veto_votes: Uint128,
+    #[serde(default)]
+    pub deposit_end_height: u64,
     pub start_height: u64,
     pub end_height: u64,
     pub status: PollStatus,
     pub total_balance_at_start_poll: Uint128,
+    #[serde(default = "default_atomic")]
+    pub atomic: bool, // reverts every execution if one of them fails
+    #[serde(default)]
+    pub kind: PollKind,
+    #[serde(default)]
+    pub choice_votes: Vec<Uint128>, // tally per option of a multiple choice poll
+    #[serde(default)]
+    pub choice_voted_amount: Uint128, // voting power that participated in a multiple choice poll
+    #[serde(default)]
+    pub winning_choice: Option<u8>,
+    #[serde(default)]
+    pub expedited: bool,
+    #[serde(default)]
+    pub finalized_early: bool,
+    #[serde(default)]
+    pub voting_reward: Uint128, // staking token split among voters pro-rata to vote weight
+    #[serde(default)]
+    pub quorum_base: QuorumBase, // method total_balance_at_start_poll was measured with
+    #[serde(default)]
+    pub actions: Vec<GovActionMsg>, // typed actions, also encoded into executions
+    #[serde(default)]
+    pub delegated_votes: Uint128, // delegated power in the recorded votes, delegators can still move it
+    #[serde(default)]
+    pub settled_voting_reward: Uint128, // voting reward claimed or swept back to the staking pool
+    #[serde(default)]
+    pub claimed_vote_amount: Uint128, // vote weight whose voting reward has been claimed
 
     pub _status: Option<PollStatus>,
 }
 
+fn default_atomic() -> bool {
+    true
+}
+
 impl Poll {
     pub fn save(&self, storage: &mut dyn Storage) -> StdResult<()> {
         POLLS.save(storage, &self.id.to_be_bytes(), self)
@@ -183,6 +518,71 @@ impl Poll {
             .collect()
     }
 
+    pub fn may_load_staker_vote(storage: &dyn Storage, address: &Addr, poll_id: u64) -> StdResult<Option<VoteInfo>> {
+        STAKER_VOTES.may_load(storage, (address, poll_id))
+    }
+
+    pub fn read_staker_votes(
+        storage: &dyn Storage,
+        address: &Addr,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<(u64, VoteInfo)>> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+        let start_after = start_after.map(Bound::exclusive);
+
+        STAKER_VOTES
+            .prefix(address)
+            .range(storage, start_after, None, Order::Ascending)
+            .take(limit)
+            .collect()
+    }
+
+    // Copies up to `limit` recorded votes after the key into the staker voting history
+    // and returns the number of copied votes with the key of the last one.
+    pub fn backfill_staker_votes(
+        storage: &mut dyn Storage,
+        start_after: Option<&(u64, Addr)>,
+        limit: u32,
+    ) -> StdResult<(u32, Option<(u64, Addr)>)> {
+        let start_after = start_after.map(|(poll_id, voter)| (poll_id.to_be_bytes(), voter.as_bytes().to_vec()));
+        let min = start_after.as_ref().map(|(poll_id, voter)| Bound::exclusive((poll_id.as_slice(), voter.as_slice())));
+
+        let votes = POLL_VOTERS
+            .range(storage, min, None, Order::Ascending)
+            .take(limit as usize)
+            .collect::<StdResult<Vec<_>>>()?;
+
+        let copied = votes.len() as u32;
+        let mut last_key = None;
+        for ((poll_id, _), vote) in votes {
+            let poll_id = u64::from_be_bytes(poll_id.as_slice().try_into()
+                .map_err(|_| StdError::generic_err("Invalid poll id"))?);
+
+            STAKER_VOTES.save(storage, (&vote.voter, poll_id), &vote)?;
+            last_key = Some((poll_id, vote.voter));
+        }
+
+        Ok((copied, last_key))
+    }
+
+    pub fn is_executed(&self) -> bool {
+        self.status == PollStatus::Executed
+            || self.status == PollStatus::PartiallyExecuted
+            || self.status == PollStatus::Failed
+    }
+
+    pub fn execution_expiry_height(&self, poll_config: &PollConfig) -> u64 {
+        self.end_height
+            .saturating_add(poll_config.execution_delay_period)
+            .saturating_add(poll_config.execution_expiry_period)
+    }
+
+    pub fn in_deposit_period(&self, block_height: u64) -> bool {
+        self.status == PollStatus::DepositPeriod && block_height <= self.deposit_end_height
+    }
+
     pub fn in_progress(&self, block_height: u64) -> bool {
         self.status == PollStatus::InProgress && block_height <= self.end_height
     }
@@ -191,32 +591,225 @@ impl Poll {
         POLL_VOTERS.load(storage, (&self.id.to_be_bytes(), address.as_str().as_bytes()))
     }
 
-    pub fn is_voted(&self, storage: &dyn Storage, address: &Addr) -> bool {
-        self.load_voter(storage, address).is_ok()
+    pub fn may_load_voter(&self, storage: &dyn Storage, address: &Addr) -> StdResult<Option<VoteInfo>> {
+        POLL_VOTERS.may_load(storage, (&self.id.to_be_bytes(), address.as_str().as_bytes()))
     }
 
-    pub fn vote(&mut self, storage: &mut dyn Storage, staker_state: &mut StakerState, vote_option: VoteOption, amount: Uint128) -> StdResult<()> {
-        let vote = VoteInfo {
-            voter: staker_state.address.clone(),
-            option: vote_option,
-            amount,
-        };
+    // casts a vote and returns the previous vote if the voter changed it.
+    // weight exceeding own_balance is counted as delegated.
+    pub fn vote(&mut self, storage: &mut dyn Storage, staker_state: &mut StakerState, votes: Vec<(VoteOption, Uint128)>, own_balance: Uint128) -> StdResult<Option<VoteInfo>> {
+        let prev_vote = self.retract_vote(storage, staker_state)?;
+
+        let mut vote = VoteInfo::new(staker_state.address.clone(), votes);
+        vote.amount = self.get_vote_weight(&vote.weights());
+        vote.delegated_amount = vote.amount.saturating_sub(own_balance);
 
-        match vote.option {
-            VoteOption::Yes => self.yes_votes += amount,
-            VoteOption::No => self.no_votes += amount,
-            VoteOption::Abstain => self.abstain_votes += amount,
+        for (option, amount) in vote.weights() {
+            match option {
+                VoteOption::Yes => self.yes_votes += amount,
+                VoteOption::No => self.no_votes += amount,
+                VoteOption::Abstain => self.abstain_votes += amount,
+                VoteOption::NoWithVeto => self.no_with_veto_votes += amount,
+                VoteOption::Choice(index) => *self.choice_votes_mut(index)? += amount,
+            }
         }
 
+        if self.is_multiple_choice() {
+            self.choice_voted_amount += vote.amount;
+        }
+
+        self.delegated_votes += vote.delegated_amount;
+
         POLL_VOTERS.save(storage, (&self.id.to_be_bytes(), vote.voter.as_str().as_bytes()), &vote)?;
+        STAKER_VOTES.save(storage, (&vote.voter, self.id), &vote)?;
 
         staker_state.vote(self.id, vote);
 
+        Ok(prev_vote)
+    }
+
+    // removes the voter's vote from the tally and returns it.
+    pub fn retract_vote(&mut self, storage: &mut dyn Storage, staker_state: &mut StakerState) -> StdResult<Option<VoteInfo>> {
+        let id = self.id.to_be_bytes();
+        let key = (id.as_slice(), staker_state.address.as_str().as_bytes());
+
+        let prev_vote = POLL_VOTERS.may_load(storage, key)?;
+
+        if let Some(prev_vote) = prev_vote.as_ref() {
+            for (option, amount) in prev_vote.weights() {
+                match option {
+                    VoteOption::Yes => self.yes_votes = self.yes_votes.checked_sub(amount)?,
+                    VoteOption::No => self.no_votes = self.no_votes.checked_sub(amount)?,
+                    VoteOption::Abstain => self.abstain_votes = self.abstain_votes.checked_sub(amount)?,
+                    VoteOption::NoWithVeto => self.no_with_veto_votes = self.no_with_veto_votes.checked_sub(amount)?,
+                    VoteOption::Choice(index) => {
+                        let choice_votes = self.choice_votes_mut(index)?;
+                        *choice_votes = choice_votes.checked_sub(amount)?;
+                    }
+                }
+            }
+
+            if self.is_multiple_choice() {
+                self.choice_voted_amount = self.choice_voted_amount.checked_sub(prev_vote.amount)?;
+            }
+
+            self.delegated_votes = self.delegated_votes.saturating_sub(prev_vote.delegated_amount);
+
+            POLL_VOTERS.remove(storage, key);
+            STAKER_VOTES.remove(storage, (&staker_state.address, self.id));
+
+            staker_state.retract_vote(self.id);
+        }
+
+        Ok(prev_vote)
+    }
+
+    // Share of the voting reward for the vote, none until the poll ends
+    pub fn get_voting_reward(&self, vote: &VoteInfo) -> Option<Uint128> {
+        let total_votes = self.get_vote_amount();
+
+        if self.voting_reward.is_zero() || total_votes.is_zero() {
+            return None;
+        }
+
+        match self.status {
+            PollStatus::DepositPeriod | PollStatus::InProgress | PollStatus::Cancelled | PollStatus::Dropped => None,
+            _ => Some(self.voting_reward.multiply_ratio(vote.amount, total_votes))
+                .filter(|reward| !reward.is_zero()),
+        }
+    }
+
+    // records a claimed voting reward and returns the rounding dust the unclaimed votes can not claim anymore.
+    // once all votes are claimed, the whole remainder is returned.
+    pub fn settle_voting_reward(&mut self, reward: Uint128, vote_amount: Uint128) -> Uint128 {
+        self.settled_voting_reward += reward;
+        self.claimed_vote_amount += vote_amount;
+
+        let total_votes = self.get_vote_amount();
+        let unclaimed_votes = total_votes.saturating_sub(self.claimed_vote_amount);
+        let claimable = if total_votes.is_zero() {
+            Uint128::zero()
+        } else {
+            self.voting_reward.multiply_ratio(unclaimed_votes, total_votes)
+        };
+
+        let dust = self.voting_reward
+            .saturating_sub(self.settled_voting_reward)
+            .saturating_sub(claimable);
+        self.settled_voting_reward += dust;
+
+        dust
+    }
+
+    pub fn required_deposit(&self, poll_config: &PollConfig) -> Uint128 {
+        if self.expedited {
+            poll_config.expedited_proposal_deposit
+        } else {
+            poll_config.proposal_deposit
+        }
+    }
+
+    pub fn voting_period(&self, poll_config: &PollConfig) -> u64 {
+        if self.expedited {
+            poll_config.expedited_voting_period
+        } else {
+            poll_config.voting_period
+        }
+    }
+
+    pub fn is_multiple_choice(&self) -> bool {
+        matches!(self.kind, PollKind::MultipleChoice { .. })
+    }
+
+    fn choice_votes_mut(&mut self, index: u8) -> StdResult<&mut Uint128> {
+        self.choice_votes.get_mut(index as usize)
+            .ok_or_else(|| StdError::generic_err(format!("Invalid vote option: choice_{}", index)))
+    }
+
+    // Voting power used by a vote. Approved options each count the full power.
+    pub fn get_vote_weight(&self, votes: &[(VoteOption, Uint128)]) -> Uint128 {
+        match &self.kind {
+            PollKind::MultipleChoice { rule: TallyRule::Approval, .. } => {
+                votes.iter().map(|(_, amount)| *amount).max().unwrap_or_default()
+            }
+            _ => votes.iter().map(|(_, amount)| *amount).sum(),
+        }
+    }
+
+    // Option with the most votes, none when no votes or tied
+    pub fn get_leading_choice(&self) -> Option<u8> {
+        let max = self.choice_votes.iter().max()?;
+        if max.is_zero() || self.choice_votes.iter().filter(|v| *v == max).count() > 1 {
+            return None;
+        }
+
+        self.choice_votes.iter().position(|v| v == max).map(|index| index as u8)
+    }
+
+    pub fn deposit(&mut self, storage: &mut dyn Storage, depositor: &Addr, amount: Uint128) -> StdResult<()> {
+        let id = self.id.to_be_bytes();
+        let key = (id.as_slice(), depositor);
+
+        let prev_amount = POLL_DEPOSITS.may_load(storage, key)?;
+
+        if prev_amount.is_none() && self.load_deposits(storage)?.len() >= MAX_DEPOSITORS {
+            return Err(StdError::generic_err("Too many depositors"));
+        }
+
+        POLL_DEPOSITS.save(storage, key, &(prev_amount.unwrap_or_default() + amount))?;
+        self.deposit_amount += amount;
+
+        Ok(())
+    }
+
+    // returns deposits per depositor. polls created before deposit tracking
+    // have the whole deposit recorded to the creator.
+    pub fn load_deposits(&self, storage: &dyn Storage) -> StdResult<Vec<(Addr, Uint128)>> {
+        let deposits = POLL_DEPOSITS
+            .prefix(&self.id.to_be_bytes())
+            .range(storage, None, None, Order::Ascending)
+            .collect::<StdResult<Vec<(Addr, Uint128)>>>()?;
+
+        if deposits.is_empty() && !self.deposit_amount.is_zero() {
+            Ok(vec![(self.creator.clone(), self.deposit_amount)])
+        } else {
+            Ok(deposits)
+        }
+    }
+
+    pub fn read_deposits(
+        storage: &dyn Storage,
+        poll_id: &u64,
+        start_after: Option<Addr>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<(Addr, Uint128)>> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+        let start_after = start_after.as_ref().map(Bound::exclusive);
+
+        POLL_DEPOSITS
+            .prefix(&poll_id.to_be_bytes())
+            .range(storage, start_after, None, Order::Ascending)
+            .take(limit)
+            .collect()
+    }
+
+    pub fn start_voting(&mut self, deps: Deps, block_height: u64, poll_config: &PollConfig) -> StdResult<()> {
+        self.status = PollStatus::InProgress;
+        self.start_height = block_height;
+        self.end_height = block_height + self.voting_period(poll_config);
+        self.quorum_base = poll_config.quorum_base.clone();
+        self.total_balance_at_start_poll = self.quorum_base.load_amount(deps, block_height - 1)?;
+
         Ok(())
     }
 
     pub fn get_vote_amount(&self) -> Uint128 {
-        self.yes_votes + self.no_votes + self.abstain_votes
+        if self.is_multiple_choice() {
+            return self.choice_voted_amount;
+        }
+
+        self.yes_votes + self.no_votes + self.abstain_votes + self.no_with_veto_votes
     }
 
     fn calculate_quorum(&self) -> (Decimal, Uint128) {
@@ -226,6 +819,70 @@ impl Poll {
         )
     }
 
+    fn get_threshold(&self, poll_config: &PollConfig) -> Decimal {
+        let threshold = poll_config.execution_policy.threshold(&self.executions)
+            .unwrap_or(poll_config.threshold);
+
+        if self.expedited {
+            threshold.max(poll_config.expedited_threshold)
+        } else {
+            threshold
+        }
+    }
+
+    // Returns the result if the voting power that has not voted yet can no longer change it.
+    // Votes can not be changed or retracted while early finalization is enabled, but delegators
+    // can still take their power back from a delegate vote and cast it on another option.
+    pub fn get_decided_result(&self, deps: Deps) -> StdResult<Option<PollResult>> {
+        let poll_config = PollConfig::load(deps.storage)?;
+
+        // The quorum base may be fixed or exclude addresses, but every xTPT holder can still vote
+        let votes = self.get_vote_amount();
+        let total_supply = load_gov_token_total_supply(deps, Some(self.start_height - 1))?;
+        let remaining = total_supply.saturating_sub(votes);
+        let delegated = self.delegated_votes;
+
+        let min_votes = votes.saturating_sub(delegated);
+        if min_votes.is_zero()
+            || self.total_balance_at_start_poll.is_zero()
+            || Decimal::from_ratio(min_votes, self.total_balance_at_start_poll) < poll_config.quorum {
+            return Ok(None);
+        }
+
+        if self.is_multiple_choice() {
+            let mut sorted_votes = self.choice_votes.clone();
+            sorted_votes.sort_unstable_by(|a, b| b.cmp(a));
+
+            let lead = sorted_votes[0] - sorted_votes.get(1).copied().unwrap_or_default();
+            return Ok(if lead > remaining + delegated + delegated { Some(PollResult::Passed) } else { None });
+        }
+
+        // Vetoed even if all the remaining and delegated power votes other options
+        let min_veto_votes = self.no_with_veto_votes.saturating_sub(delegated);
+        let min_veto_ratio = Decimal::from_ratio(min_veto_votes, votes + remaining);
+        if !min_veto_votes.is_zero() && min_veto_ratio > poll_config.veto_threshold {
+            return Ok(Some(PollResult::Vetoed));
+        }
+
+        // Passed even if all the remaining and delegated power votes no or veto
+        let min_yes_votes = self.yes_votes.saturating_sub(delegated);
+        if min_yes_votes.is_zero() {
+            return Ok(None);
+        }
+
+        let swing = remaining + delegated;
+        let max_veto_ratio = Decimal::from_ratio(self.no_with_veto_votes + swing, min_votes + remaining);
+        let min_yes_ratio = Decimal::from_ratio(
+            min_yes_votes,
+            self.yes_votes + self.no_votes + self.no_with_veto_votes + swing,
+        );
+        if max_veto_ratio <= poll_config.veto_threshold && min_yes_ratio > self.get_threshold(&poll_config) {
+            return Ok(Some(PollResult::Passed));
+        }
+
+        Ok(None)
+    }
+
     pub fn get_result(&self, deps: Deps) -> StdResult<(PollResult, Uint128)> {
         let poll_config = PollConfig::load(deps.storage)?;
 
@@ -242,38 +899,120 @@ impl Poll {
             return Ok((PollResult::QuorumNotReached, gov_token_total_supply));
         }
 
+        // Multiple choice: The option with the most votes wins, there is no winner on a tie.
+        if self.is_multiple_choice() {
+            return match self.get_leading_choice() {
+                Some(_) => Ok((PollResult::Passed, gov_token_total_supply)),
+                None => Ok((PollResult::Tie, gov_token_total_supply)),
+            };
+        }
+
+        // Veto: More than veto threshold of the tokens that participated in the vote
+        // rejects the poll regardless of the yes votes.
+        let veto_ratio = Decimal::from_ratio(self.no_with_veto_votes, votes);
+        if !self.no_with_veto_votes.is_zero() && veto_ratio > poll_config.veto_threshold {
+            return Ok((PollResult::Vetoed, gov_token_total_supply));
+        }
+
         //TODO: 통과 기준이 threshold 이상인지 초과인지 확인 필요
         //Threshold: More than 50% of the tokens that participated in the vote
-        let yes_ratio = Decimal::from_ratio(self.yes_votes, self.no_votes + self.yes_votes);
-        if yes_ratio <= poll_config.threshold {
+        let threshold = self.get_threshold(&poll_config);
+        let yes_ratio = Decimal::from_ratio(self.yes_votes, self.no_votes + self.no_with_veto_votes + self.yes_votes);
+        if yes_ratio <= threshold {
             return Ok((PollResult::ThresholdNotReached, gov_token_total_supply));
         }
 
         Ok((PollResult::Passed, gov_token_total_supply))
     }
 
+    // Everything end_poll decides at the given height
+    pub fn tally(&self, deps: Deps, block_height: u64) -> StdResult<PollTally> {
+        let poll_config = PollConfig::load(deps.storage)?;
+
+        let (result, total_balance) = self.get_result(deps)?;
+
+        let quorum = if self.total_balance_at_start_poll.is_zero() {
+            Decimal::zero()
+        } else {
+            self.calculate_quorum().0
+        };
+
+        let yes_ratio = if self.yes_votes.is_zero() {
+            Decimal::zero()
+        } else {
+            Decimal::from_ratio(self.yes_votes, self.no_votes + self.no_with_veto_votes + self.yes_votes)
+        };
+
+        let finalized_early = self.end_height > block_height
+            && poll_config.early_finalization
+            && self.get_decided_result(deps)?.is_some();
+
+        let expedited_fallback = self.expedited
+            && (result == PollResult::ThresholdNotReached || result == PollResult::QuorumNotReached);
+
+        let deposit_outcome = if expedited_fallback {
+            DepositOutcome::Pending
+        } else {
+            match result {
+                PollResult::Vetoed => DepositOutcome::Confiscated,
+                PollResult::QuorumNotReached => DepositOutcome::Retained,
+                _ => DepositOutcome::Refunded,
+            }
+        };
+
+        Ok(PollTally {
+            quorum,
+            yes_ratio,
+            result,
+            total_balance,
+            can_end: self.end_height <= block_height || finalized_early,
+            finalized_early,
+            expedited_fallback,
+            deposit_outcome,
+        })
+    }
+
     pub fn to_response(&self) -> PollResponse {
         PollResponse {
             id: self.id,
             title: self.title.to_string(),
             description: self.description.to_string(),
             link: self.link.clone(),
-            executions: self.executions.iter().map(|v| ExecutionMsg {
-                order: v.order,
-                contract: v.contract.to_string(),
-                msg: v.msg.clone(),
-            }).collect(),
+            executions: self.executions.iter().map(ExecutionMsg::from).collect(),
             creator: self.creator.to_string(),
             deposit_amount: self.deposit_amount,
             yes_votes: self.yes_votes,
             no_votes: self.no_votes,
             abstain_votes: self.abstain_votes,
+            no_with_veto_votes: self.no_with_veto_votes,
+            deposit_end_height: self.deposit_end_height,
             start_height: self.start_height,
             end_height: self.end_height,
             status: self.status.clone(),
             total_balance_at_start_poll: self.total_balance_at_start_poll,
+            atomic: self.atomic,
+            kind: self.kind.clone(),
+            choice_votes: self.choice_votes.clone(),
+            winning_choice: self.winning_choice,
+            expedited: self.expedited,
+            finalized_early: self.finalized_early,
+            voting_reward: self.voting_reward,
+            quorum_base: self.quorum_base.to_msg(),
+            actions: self.actions.clone(),
         }
     }
+
+    pub fn save_execution_result(&self, storage: &mut dyn Storage, result: &ExecutionResult) -> StdResult<()> {
+        POLL_EXECUTION_RESULTS.save(storage, (&self.id.to_be_bytes(), &result.order.to_be_bytes()), result)
+    }
+
+    pub fn load_execution_results(storage: &dyn Storage, poll_id: &u64) -> StdResult<Vec<ExecutionResult>> {
+        POLL_EXECUTION_RESULTS
+            .prefix(&poll_id.to_be_bytes())
+            .range(storage, None, None, Order::Ascending)
+            .map(|item| item.map(|(_, result)| result))
+            .collect()
+    }
 }
 
 
@@ -304,11 +1043,94 @@ impl PollExecutionContext {
     }
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExecutionResult {
+    pub order: u64,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+impl ExecutionResult {
+    pub fn to_msg(&self) -> ExecutionResultMsg {
+        ExecutionResultMsg {
+            order: self.order,
+            success: self.success,
+            error: self.error.clone(),
+        }
+    }
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct VoteInfo {
     pub voter: Addr,
-    pub option: VoteOption,
-    pub amount: Uint128,
+    pub option: VoteOption, // the option with the largest weight
+    pub amount: Uint128, // total weight voted
+    #[serde(default)]
+    pub split: Vec<(VoteOption, Uint128)>, // weight per option, empty if not split
+    #[serde(default)]
+    pub delegated_amount: Uint128, // part of amount voted with delegated power
+}
+
+impl VoteInfo {
+    pub fn new(voter: Addr, votes: Vec<(VoteOption, Uint128)>) -> VoteInfo {
+        let amount = votes.iter().map(|(_, amount)| *amount).sum();
+        let option = votes.iter()
+            .max_by_key(|(_, amount)| *amount)
+            .map(|(option, _)| option.clone())
+            .unwrap_or(VoteOption::Abstain);
+
+        VoteInfo {
+            voter,
+            option,
+            amount,
+            split: if votes.len() > 1 { votes } else { vec![] },
+            delegated_amount: Uint128::zero(),
+        }
+    }
+
+    pub fn own_amount(&self) -> Uint128 {
+        self.amount.saturating_sub(self.delegated_amount)
+    }
+
+    pub fn weights(&self) -> Vec<(VoteOption, Uint128)> {
+        if self.split.is_empty() {
+            vec![(self.option.clone(), self.amount)]
+        } else {
+            self.split.clone()
+        }
+    }
+
+    pub fn options_to_string(&self) -> String {
+        if self.split.is_empty() {
+            self.option.to_string()
+        } else {
+            self.split.iter()
+                .map(|(option, amount)| format!("{}:{}", option, amount))
+                .collect::<Vec<String>>()
+                .join(",")
+        }
+    }
+
+    pub fn to_msg(&self) -> VoteInfoMsg {
+        VoteInfoMsg {
+            voter: self.voter.to_string(),
+            option: self.option.clone(),
+            amount: self.amount,
+            split: self.weights(),
+            delegated_amount: self.delegated_amount,
+        }
+    }
+}
+
+pub struct PollTally {
+    pub quorum: Decimal,
+    pub yes_ratio: Decimal,
+    pub result: PollResult,
+    pub total_balance: Uint128,
+    pub can_end: bool,
+    pub finalized_early: bool,
+    pub expedited_fallback: bool,
+    pub deposit_outcome: DepositOutcome,
 }
 
 #[derive(PartialEq)]
@@ -316,6 +1138,9 @@ pub enum PollResult {
     Passed,
     QuorumNotReached,
     ThresholdNotReached,
+    Vetoed,
+    DepositNotReached,
+    Tie,
 }
 
 impl fmt::Display for PollResult {
@@ -324,6 +1149,9 @@ impl fmt::Display for PollResult {
             PollResult::Passed => fmt.write_str("passed"),
             PollResult::QuorumNotReached => fmt.write_str("Quorum not reached"),
             PollResult::ThresholdNotReached => fmt.write_str("Threshold not reached"),
+            PollResult::Vetoed => fmt.write_str("Vetoed"),
+            PollResult::DepositNotReached => fmt.write_str("Deposit not reached"),
+            PollResult::Tie => fmt.write_str("Tie"),
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,104 @@
+use cosmwasm_std::{attr, CosmosMsg, Env, MessageInfo, Response, SubMsg, to_binary, Uint128, WasmMsg};
+use cosmwasm_std::testing::mock_info;
+use cw20::Cw20ExecuteMsg;
+
+use terrapoker::common::ContractResult;
+use terrapoker::governance::enumerations::{PollStatus, VoteOption};
+use terrapoker::mock_querier::{custom_deps, CustomDeps};
+use terrapoker::test_constants::governance::*;
+use terrapoker::test_utils::{expect_generic_err, expect_unauthorized_err};
+
+use crate::poll::executions::cancel_poll;
+use crate::poll::states::{Poll, PollState};
+use crate::poll::tests::cast_vote::VOTER1;
+use crate::poll::tests::create_poll::PROPOSER1;
+use crate::tests::{init_default, POLL_CANCEL_SLASH_PERCENT};
+
+pub fn exec(
+    deps: &mut CustomDeps,
+    env: Env,
+    info: MessageInfo,
+    poll_id: u64,
+) -> ContractResult<Response> {
+    cancel_poll(deps.as_mut(), env, info, poll_id)
+}
+
+#[test]
+fn succeed() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    super::create_poll::default(&mut deps);
+
+    let poll_id = 1u64;
+
+    let response = exec(&mut deps, governance_env(), mock_info(PROPOSER1, &[]), poll_id).unwrap();
+
+    let slashed_amount = POLL_PROPOSAL_DEPOSIT.multiply_ratio(POLL_CANCEL_SLASH_PERCENT, 100u64);
+    let refunded_amount = POLL_PROPOSAL_DEPOSIT.checked_sub(slashed_amount).unwrap();
+
+    assert_eq!(response.messages, vec![
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: GOVERNANCE_TOKEN.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: PROPOSER1.to_string(),
+                amount: refunded_amount,
+            }).unwrap(),
+        })),
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: GOVERNANCE_TOKEN.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Burn {
+                amount: slashed_amount,
+            }).unwrap(),
+        })),
+    ]);
+    assert_eq!(response.attributes, vec![
+        attr("action", "cancel_poll"),
+        attr("poll_id", poll_id.to_string()),
+        attr("refunded_amount", refunded_amount.to_string()),
+        attr("slashed_amount", slashed_amount.to_string()),
+    ]);
+
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    assert_eq!(poll.status, PollStatus::Cancelled);
+
+    let polls = Poll::query(&deps.storage, Some(PollStatus::Cancelled), None, None, None).unwrap();
+    assert_eq!(polls.len(), 1);
+
+    let poll_state = PollState::load(&deps.storage).unwrap();
+    assert_eq!(poll_state.total_deposit, Uint128::zero());
+}
+
+#[test]
+fn failed_not_creator() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    super::create_poll::default(&mut deps);
+
+    let result = exec(&mut deps, governance_env(), mock_info(VOTER1, &[]), 1);
+
+    expect_unauthorized_err(&result);
+}
+
+#[test]
+fn failed_already_voted() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    super::create_poll::default(&mut deps);
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER1, Uint128::new(100));
+
+    let poll_id = 1u64;
+
+    super::cast_vote::will_success(&mut deps, VOTER1, poll_id, VoteOption::Yes, Uint128::new(100));
+
+    let result = exec(&mut deps, governance_env(), mock_info(PROPOSER1, &[]), poll_id);
+
+    expect_generic_err(&result, "Poll already has votes");
+}
@@ -1,15 +1,15 @@
-use cosmwasm_std::{Addr, Env, MessageInfo, Response, Uint128};
+use cosmwasm_std::{Addr, attr, Env, MessageInfo, Response, Uint128};
 use cosmwasm_std::testing::mock_info;
 
 use terrapoker::common::ContractResult;
-use terrapoker::governance::enumerations::VoteOption;
+use terrapoker::governance::enumerations::{TallyRule, VoteOption};
 use terrapoker::mock_querier::{custom_deps, CustomDeps};
 use terrapoker::test_constants::governance::governance_env;
 use terrapoker::test_utils::expect_generic_err;
 
-use crate::poll::executions::cast_vote;
+use crate::poll::executions::{cast_split_vote, cast_vote};
 use crate::poll::states::{Poll, VoteInfo};
-use crate::staking::states::StakerState;
+use crate::staking::states::{MAX_VOTES_IN_PROGRESS, StakerState};
 use crate::tests::init_default;
 
 pub const VOTER1: &str = "terra17q4lzg70un58uefr2fwu7uxtgvftspr7d0a6p3";
@@ -34,6 +34,22 @@ pub fn exec(
     )
 }
 
+pub fn exec_split(
+    deps: &mut CustomDeps,
+    env: Env,
+    info: MessageInfo,
+    poll_id: u64,
+    votes: Vec<(VoteOption, Uint128)>,
+) -> ContractResult<Response> {
+    cast_split_vote(
+        deps.as_mut(),
+        env,
+        info,
+        poll_id,
+        votes,
+    )
+}
+
 pub fn will_success(
     deps: &mut CustomDeps,
     voter: &str,
@@ -76,9 +92,11 @@ fn succeed() {
         voter: voter_addr.clone(),
         option: vote_option.clone(),
         amount: vote_amount,
+        split: vec![],
+        delegated_amount: Uint128::zero(),
     };
 
-    let staker_state = StakerState::load(&deps.storage, &voter_addr).unwrap();
+    let staker_state = StakerState::load_safe(&deps.storage, &voter_addr).unwrap();
     assert_eq!(staker_state.votes, vec![(poll_id, vote_info.clone())]);
 
     let poll = Poll::load(&deps.storage, &poll_id).unwrap();
@@ -135,7 +153,7 @@ fn failed_cast_vote_without_poll() {
 }
 
 #[test]
-fn failed_cast_vote_twice() {
+fn succeed_change_vote() {
     let mut deps = custom_deps();
 
     init_default(deps.as_mut());
@@ -143,6 +161,7 @@ fn failed_cast_vote_twice() {
     super::create_poll::default(&mut deps);
     crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER1, Uint128::new(100));
 
+    let voter_addr = Addr::unchecked(VOTER1);
     let poll_id = 1u64;
 
     will_success(
@@ -153,14 +172,236 @@ fn failed_cast_vote_twice() {
         Uint128::new(10),
     );
 
-    let result = exec(
+    let (_, _, response) = will_success(
+        &mut deps,
+        VOTER1,
+        poll_id,
+        VoteOption::No,
+        Uint128::new(30),
+    );
+    assert_eq!(response.attributes, vec![
+        attr("action", "cast_vote"),
+        attr("poll_id", poll_id.to_string()),
+        attr("amount", "30"),
+        attr("voter", VOTER1),
+        attr("voter_option", "no"),
+        attr("prev_amount", "10"),
+        attr("prev_voter_option", "yes"),
+    ]);
+
+    let vote_info = VoteInfo {
+        voter: voter_addr.clone(),
+        option: VoteOption::No,
+        amount: Uint128::new(30),
+        split: vec![],
+        delegated_amount: Uint128::zero(),
+    };
+
+    let staker_state = StakerState::load_safe(&deps.storage, &voter_addr).unwrap();
+    assert_eq!(staker_state.votes, vec![(poll_id, vote_info.clone())]);
+
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    assert_eq!(poll.yes_votes, Uint128::zero());
+    assert_eq!(poll.no_votes, Uint128::new(30));
+
+    let voter = poll.load_voter(&deps.storage, &voter_addr).unwrap();
+    assert_eq!(voter, vote_info);
+}
+
+#[test]
+fn succeed_split_vote() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    super::create_poll::default(&mut deps);
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER1, Uint128::new(100));
+
+    let voter_addr = Addr::unchecked(VOTER1);
+    let poll_id = 1u64;
+    let votes = vec![
+        (VoteOption::Yes, Uint128::new(60)),
+        (VoteOption::No, Uint128::new(30)),
+        (VoteOption::Abstain, Uint128::new(10)),
+    ];
+
+    let response = exec_split(
         &mut deps,
         governance_env(),
         mock_info(VOTER1, &[]),
         poll_id,
-        VoteOption::Yes,
-        Uint128::new(10),
+        votes.clone(),
+    ).unwrap();
+    assert_eq!(response.attributes, vec![
+        attr("action", "cast_vote"),
+        attr("poll_id", poll_id.to_string()),
+        attr("amount", "100"),
+        attr("voter", VOTER1),
+        attr("voter_option", "yes:60,no:30,abstain:10"),
+    ]);
+
+    let vote_info = VoteInfo {
+        voter: voter_addr.clone(),
+        option: VoteOption::Yes,
+        amount: Uint128::new(100),
+        split: votes,
+        delegated_amount: Uint128::zero(),
+    };
+
+    let staker_state = StakerState::load_safe(&deps.storage, &voter_addr).unwrap();
+    assert_eq!(staker_state.votes, vec![(poll_id, vote_info.clone())]);
+    assert_eq!(staker_state.get_vote_locked_balance(), Uint128::new(100));
+
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    assert_eq!(poll.yes_votes, Uint128::new(60));
+    assert_eq!(poll.no_votes, Uint128::new(30));
+    assert_eq!(poll.abstain_votes, Uint128::new(10));
+}
+
+#[test]
+fn failed_split_vote_not_enough_staked() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    super::create_poll::default(&mut deps);
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER1, Uint128::new(100));
+
+    let result = exec_split(
+        &mut deps,
+        governance_env(),
+        mock_info(VOTER1, &[]),
+        1,
+        vec![
+            (VoteOption::Yes, Uint128::new(60)),
+            (VoteOption::No, Uint128::new(41)),
+        ],
     );
 
-    expect_generic_err(&result, "User has already voted.");
-}
\ No newline at end of file
+    expect_generic_err(&result, "User does not have enough staked tokens.");
+}
+
+#[test]
+fn failed_split_vote_duplicated_option() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    super::create_poll::default(&mut deps);
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER1, Uint128::new(100));
+
+    let result = exec_split(
+        &mut deps,
+        governance_env(),
+        mock_info(VOTER1, &[]),
+        1,
+        vec![
+            (VoteOption::Yes, Uint128::new(60)),
+            (VoteOption::Yes, Uint128::new(40)),
+        ],
+    );
+
+    expect_generic_err(&result, "Duplicated vote option: yes");
+}
+
+#[test]
+fn succeed_approval_vote() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    super::create_poll::will_success_multiple_choice(&mut deps, vec!["Holdem", "Omaha", "Stud"], TallyRule::Approval);
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER1, Uint128::new(100));
+
+    exec_split(
+        &mut deps,
+        governance_env(),
+        mock_info(VOTER1, &[]),
+        1,
+        vec![
+            (VoteOption::Choice(0), Uint128::new(100)),
+            (VoteOption::Choice(2), Uint128::new(100)),
+        ],
+    ).unwrap();
+
+    let poll = Poll::load(&deps.storage, &1).unwrap();
+    assert_eq!(poll.choice_votes, vec![Uint128::new(100), Uint128::zero(), Uint128::new(100)]);
+    assert_eq!(poll.get_vote_amount(), Uint128::new(100));
+
+    let staker_state = StakerState::load_safe(&deps.storage, &Addr::unchecked(VOTER1)).unwrap();
+    assert_eq!(staker_state.get_vote_locked_balance(), Uint128::new(100));
+}
+
+#[test]
+fn failed_invalid_choice() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    super::create_poll::will_success_multiple_choice(&mut deps, vec!["Holdem", "Omaha"], TallyRule::Plurality);
+    super::create_poll::default(&mut deps);
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER1, Uint128::new(100));
+
+    let result = exec(&mut deps, governance_env(), mock_info(VOTER1, &[]), 1, VoteOption::Yes, Uint128::new(100));
+    expect_generic_err(&result, "Invalid vote option: yes");
+
+    let result = exec(&mut deps, governance_env(), mock_info(VOTER1, &[]), 1, VoteOption::Choice(2), Uint128::new(100));
+    expect_generic_err(&result, "Invalid vote option: choice_2");
+
+    let result = exec_split(
+        &mut deps,
+        governance_env(),
+        mock_info(VOTER1, &[]),
+        1,
+        vec![
+            (VoteOption::Choice(0), Uint128::new(50)),
+            (VoteOption::Choice(1), Uint128::new(50)),
+        ],
+    );
+    expect_generic_err(&result, "Plurality poll allows a single option");
+
+    let result = exec(&mut deps, governance_env(), mock_info(VOTER1, &[]), 2, VoteOption::Choice(0), Uint128::new(100));
+    expect_generic_err(&result, "Invalid vote option: choice_0");
+}
+
+#[test]
+fn failed_zero_amount_vote() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    super::create_poll::default(&mut deps);
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER1, Uint128::new(100));
+
+    let result = exec_split(
+        &mut deps,
+        governance_env(),
+        mock_info(VOTER1, &[]),
+        1,
+        vec![
+            (VoteOption::Yes, Uint128::new(100)),
+            (VoteOption::No, Uint128::zero()),
+        ],
+    );
+
+    expect_generic_err(&result, "Vote amount must be greater than zero: no");
+}
+
+#[test]
+fn failed_too_many_votes_in_progress() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    super::create_poll::default(&mut deps);
+    super::create_poll::default(&mut deps);
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER1, Uint128::new(100));
+
+    let vote = VoteInfo::new(Addr::unchecked(VOTER1), vec![(VoteOption::Yes, Uint128::new(1))]);
+    let mut staker_state = StakerState::load_safe(&deps.storage, &Addr::unchecked(VOTER1)).unwrap();
+    staker_state.votes = vec![(1, vote); MAX_VOTES_IN_PROGRESS];
+    staker_state.save(&mut deps.storage).unwrap();
+
+    let result = exec(&mut deps, governance_env(), mock_info(VOTER1, &[]), 2, VoteOption::Yes, Uint128::new(100));
+    expect_generic_err(&result, &format!("Can not vote on more than {} polls in progress", MAX_VOTES_IN_PROGRESS));
+}
@@ -1,17 +1,19 @@
-use cosmwasm_std::{Addr, attr, Env, MessageInfo, Response, to_binary, Uint128, StdResult, Binary};
+use cosmwasm_std::{Addr, attr, Env, from_slice, MessageInfo, Response, to_binary, to_vec, Uint128, StdResult, Binary};
 use cosmwasm_std::testing::mock_info;
 use cw20::Cw20ExecuteMsg;
+use terrapoker::governance::execute_msgs::ExecuteMsg;
 
 use terrapoker::common::{ContractResult, Execution, ExecutionMsg};
-use terrapoker::governance::enumerations::PollStatus;
+use terrapoker::governance::actions::{GovAction, GovActionMsg};
+use terrapoker::governance::enumerations::{PollKind, PollStatus, TallyRule};
 use terrapoker::mock_querier::{custom_deps, CustomDeps};
 use terrapoker::test_constants::default_sender;
 use terrapoker::test_constants::governance::*;
 use terrapoker::test_utils::{expect_generic_err, expect_unauthorized_err};
 
 use crate::poll::executions::create_poll;
-use crate::poll::states::Poll;
-use crate::tests::init_default;
+use crate::poll::states::{Poll, PollConfig, QuorumBase};
+use crate::tests::{init_default, POLL_DEPOSIT_PERIOD, POLL_EXPEDITED_PROPOSAL_DEPOSIT, POLL_EXPEDITED_VOTING_PERIOD};
 
 pub const PROPOSER1: &str = "terra14mtctaszgzm4gcedlfslds802fmklnp4up72da";
 
@@ -29,6 +31,41 @@ pub fn exec(
     description: String,
     link: Option<String>,
     execution_msgs: Vec<ExecutionMsg>,
+) -> ContractResult<Response> {
+    exec_with_options(deps, env, info, proposer, deposit_amount, title, description, link, execution_msgs, None, None, None)
+}
+
+pub fn exec_with_options(
+    deps: &mut CustomDeps,
+    env: Env,
+    info: MessageInfo,
+    proposer: Addr,
+    deposit_amount: Uint128,
+    title: String,
+    description: String,
+    link: Option<String>,
+    execution_msgs: Vec<ExecutionMsg>,
+    atomic: Option<bool>,
+    kind: Option<PollKind>,
+    expedited: Option<bool>,
+) -> ContractResult<Response> {
+    exec_with_actions(deps, env, info, proposer, deposit_amount, title, description, link, execution_msgs, atomic, kind, expedited, None)
+}
+
+pub fn exec_with_actions(
+    deps: &mut CustomDeps,
+    env: Env,
+    info: MessageInfo,
+    proposer: Addr,
+    deposit_amount: Uint128,
+    title: String,
+    description: String,
+    link: Option<String>,
+    execution_msgs: Vec<ExecutionMsg>,
+    atomic: Option<bool>,
+    kind: Option<PollKind>,
+    expedited: Option<bool>,
+    actions: Option<Vec<GovActionMsg>>,
 ) -> ContractResult<Response> {
     deps.querier.plus_token_balances(&[(
         GOVERNANCE_TOKEN,
@@ -45,6 +82,10 @@ pub fn exec(
         description,
         link,
         execution_msgs,
+        atomic,
+        kind,
+        expedited,
+        actions,
     )
 }
 
@@ -127,6 +168,8 @@ fn succeed() {
         yes_votes: Uint128::zero(),
         no_votes: Uint128::zero(),
         abstain_votes: Uint128::zero(),
+        no_with_veto_votes: Uint128::zero(),
+        deposit_end_height: env.block.height + POLL_DEPOSIT_PERIOD,
         start_height: env.block.height,
         end_height: env.block.height + POLL_VOTING_PERIOD,
         title: POLL_TITLE.to_string(),
@@ -134,7 +177,20 @@ fn succeed() {
         link: Some(POLL_LINK.to_string()),
         executions,
         deposit_amount: POLL_PROPOSAL_DEPOSIT,
-        total_balance_at_start_poll: None,
+        total_balance_at_start_poll: Uint128::zero(),
+        atomic: true,
+        kind: PollKind::Binary {},
+        choice_votes: vec![],
+        choice_voted_amount: Uint128::zero(),
+        winning_choice: None,
+        expedited: false,
+        finalized_early: false,
+        voting_reward: Uint128::zero(),
+        quorum_base: QuorumBase::TotalSupply {},
+        actions: vec![],
+        delegated_votes: Uint128::zero(),
+        settled_voting_reward: Uint128::zero(),
+        claimed_vote_amount: Uint128::zero(),
         _status: Some(PollStatus::InProgress),
     });
 
@@ -170,6 +226,40 @@ fn failed_invalid_permission() {
     expect_unauthorized_err(&result);
 }
 
+#[test]
+fn succeed_deposit_period() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    let deposit_amount = POLL_PROPOSAL_DEPOSIT.checked_sub(Uint128::new(1)).unwrap();
+
+    let (env, _, response) = will_success(
+        &mut deps,
+        PROPOSER1,
+        deposit_amount,
+        POLL_TITLE,
+        POLL_DESCRIPTION,
+        None,
+        vec![],
+    );
+
+    assert_eq!(response.attributes, vec![
+        attr("action", "create_poll"),
+        attr("creator", PROPOSER1),
+        attr("poll_id", "1"),
+        attr("end_height", (env.block.height + POLL_VOTING_PERIOD).to_string()),
+        attr("deposit_end_height", (env.block.height + POLL_DEPOSIT_PERIOD).to_string()),
+    ]);
+
+    let poll = Poll::load(&deps.storage, &1).unwrap();
+    assert_eq!(poll.status, PollStatus::DepositPeriod);
+    assert_eq!(poll.deposit_amount, deposit_amount);
+    assert_eq!(poll.load_deposits(&deps.storage).unwrap(), vec![
+        (Addr::unchecked(PROPOSER1), deposit_amount),
+    ]);
+}
+
 #[test]
 fn failed_create_poll_invalid_deposit() {
     let mut deps = custom_deps();
@@ -181,17 +271,14 @@ fn failed_create_poll_invalid_deposit() {
         governance_env(),
         mock_info(GOVERNANCE_TOKEN, &[]),
         Addr::unchecked(PROPOSER1),
-        POLL_PROPOSAL_DEPOSIT.checked_sub(Uint128::new(1)).unwrap(),
+        Uint128::zero(),
         POLL_TITLE.to_string(),
         POLL_DESCRIPTION.to_string(),
         None,
         vec![],
     );
 
-    expect_generic_err(
-        &result,
-        format!("Must deposit more than {} token", POLL_PROPOSAL_DEPOSIT).as_str(),
-    );
+    expect_generic_err(&result, "Must deposit more than 0 token");
 }
 
 #[test]
@@ -300,13 +387,14 @@ fn failed_create_poll_transfer() {
     init_default(deps.as_mut());
 
     let executions = vec![
-        ExecutionMsg {
+        ExecutionMsg::Execute {
             order: 0,
             contract: GOVERNANCE_TOKEN.to_string(),
             msg: to_binary(&Cw20ExecuteMsg::Transfer {
                 amount: Uint128::new(1),
                 recipient: GOVERNANCE.to_string(),
             }).unwrap(),
+            funds: vec![],
         }
     ];
 
@@ -321,10 +409,10 @@ fn failed_create_poll_transfer() {
         None,
         executions,
     );
-    expect_generic_err(&result, "Can't use Transfer message");
+    expect_generic_err(&result, &format!("Execution message not allowed: transfer on {}", GOVERNANCE_TOKEN));
 
     let executions = vec![
-        ExecutionMsg {
+        ExecutionMsg::Execute {
             order: 0,
             contract: GOVERNANCE_TOKEN.to_string(),
             msg: to_binary(&Cw20ExecuteMsg::Send {
@@ -332,6 +420,7 @@ fn failed_create_poll_transfer() {
                 contract: GOVERNANCE.to_string(),
                 msg: Binary::default(),
             }).unwrap(),
+            funds: vec![],
         }
     ];
 
@@ -346,10 +435,10 @@ fn failed_create_poll_transfer() {
         None,
         executions,
     );
-    expect_generic_err(&result, "Can't use Send message");
+    expect_generic_err(&result, &format!("Execution message not allowed: send on {}", GOVERNANCE_TOKEN));
 
     let executions = vec![
-        ExecutionMsg {
+        ExecutionMsg::Execute {
             order: 0,
             contract: GOVERNANCE_TOKEN.to_string(),
             msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
@@ -357,6 +446,7 @@ fn failed_create_poll_transfer() {
                 spender: GOVERNANCE.to_string(),
                 expires: None,
             }).unwrap(),
+            funds: vec![],
         }
     ];
 
@@ -371,7 +461,438 @@ fn failed_create_poll_transfer() {
         None,
         executions,
     );
-    expect_generic_err(&result, "Can't use IncreaseAllowance message");
+    expect_generic_err(&result, &format!("Execution message not allowed: increase_allowance on {}", GOVERNANCE_TOKEN));
+}
+
+#[test]
+fn failed_escaped_execution_message() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    let executions = vec![
+        ExecutionMsg::Execute {
+            order: 0,
+            contract: GOVERNANCE_TOKEN.to_string(),
+            msg: Binary::from(br#"{"tr\u0061nsfer_from":{"owner":"a","recipient":"b","amount":"1"}}"#.as_slice()),
+            funds: vec![],
+        }
+    ];
+
+    let result = exec(
+        &mut deps,
+        governance_env(),
+        mock_info(GOVERNANCE_TOKEN, &[]),
+        Addr::unchecked(PROPOSER1),
+        POLL_PROPOSAL_DEPOSIT,
+        POLL_TITLE.to_string(),
+        POLL_DESCRIPTION.to_string(),
+        None,
+        executions,
+    );
+    expect_generic_err(&result, &format!("Execution message not allowed: transfer_from on {}", GOVERNANCE_TOKEN));
+}
+
+#[test]
+fn failed_invalid_execution_message() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    for msg in [b"not json".as_slice(), b"{}", br#"{"burn":{},"transfer":{}}"#] {
+        let executions = vec![
+            ExecutionMsg::Execute {
+                order: 0,
+                contract: GOVERNANCE_TOKEN.to_string(),
+                msg: Binary::from(msg),
+                funds: vec![],
+            }
+        ];
+
+        let result = exec(
+            &mut deps,
+            governance_env(),
+            mock_info(GOVERNANCE_TOKEN, &[]),
+            Addr::unchecked(PROPOSER1),
+            POLL_PROPOSAL_DEPOSIT,
+            POLL_TITLE.to_string(),
+            POLL_DESCRIPTION.to_string(),
+            None,
+            executions,
+        );
+        assert!(result.is_err());
+    }
+}
+
+#[test]
+fn failed_execution_target_not_allowed() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    let mut poll_config = PollConfig::load(&deps.storage).unwrap();
+    poll_config.execution_policy.allow_any_target = false;
+    poll_config.save(&mut deps.storage).unwrap();
+
+    let execution = mock_exec_msg(1);
+
+    let result = exec(
+        &mut deps,
+        governance_env(),
+        mock_info(GOVERNANCE_TOKEN, &[]),
+        Addr::unchecked(PROPOSER1),
+        POLL_PROPOSAL_DEPOSIT,
+        POLL_TITLE.to_string(),
+        POLL_DESCRIPTION.to_string(),
+        None,
+        vec![execution],
+    );
+    expect_generic_err(&result, "Execution target not allowed: terra1333veey879eeqcff8j3gfcgwt8cfrg9mq20v6f");
+}
+
+#[test]
+fn failed_too_many_open_polls() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    let mut poll_config = PollConfig::load(&deps.storage).unwrap();
+    poll_config.max_polls_per_proposer = 1;
+    poll_config.save(&mut deps.storage).unwrap();
+
+    default(&mut deps);
+
+    let result = exec(
+        &mut deps,
+        governance_env(),
+        mock_info(GOVERNANCE_TOKEN, &[]),
+        Addr::unchecked(PROPOSER1),
+        POLL_PROPOSAL_DEPOSIT,
+        POLL_TITLE.to_string(),
+        POLL_DESCRIPTION.to_string(),
+        None,
+        vec![],
+    );
+    expect_generic_err(&result, "Too many open polls");
+}
+
+#[test]
+fn failed_proposal_cooldown() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    let mut poll_config = PollConfig::load(&deps.storage).unwrap();
+    poll_config.proposal_cooldown_period = 100;
+    poll_config.save(&mut deps.storage).unwrap();
+
+    let (env, _, _) = default(&mut deps);
+
+    let result = exec(
+        &mut deps,
+        governance_env_height(env.block.height + 99),
+        mock_info(GOVERNANCE_TOKEN, &[]),
+        Addr::unchecked(PROPOSER1),
+        POLL_PROPOSAL_DEPOSIT,
+        POLL_TITLE.to_string(),
+        POLL_DESCRIPTION.to_string(),
+        None,
+        vec![],
+    );
+    expect_generic_err(&result, "Poll creation is in cooldown");
+
+    exec(
+        &mut deps,
+        governance_env_height(env.block.height + 100),
+        mock_info(GOVERNANCE_TOKEN, &[]),
+        Addr::unchecked(PROPOSER1),
+        POLL_PROPOSAL_DEPOSIT,
+        POLL_TITLE.to_string(),
+        POLL_DESCRIPTION.to_string(),
+        None,
+        vec![],
+    ).unwrap();
+}
+
+#[test]
+fn succeed_fixed_quorum_base() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    let quorum_base = QuorumBase::Fixed { amount: Uint128::new(1_000_000) };
+
+    let mut poll_config = PollConfig::load(&deps.storage).unwrap();
+    poll_config.quorum_base = quorum_base.clone();
+    poll_config.save(&mut deps.storage).unwrap();
+
+    default(&mut deps);
+
+    let poll = Poll::load(&deps.storage, &1u64).unwrap();
+    assert_eq!(poll.quorum_base, quorum_base);
+    assert_eq!(poll.total_balance_at_start_poll, Uint128::new(1_000_000));
+}
+
+#[test]
+fn succeed_gov_actions() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    let action = GovActionMsg {
+        order: 2,
+        action: GovAction::UpdateStakingConfig {
+            distributor: None,
+            unstake_lock_period: Some(100),
+            instant_unstake_fee: None,
+            instant_unstake_fee_recipient: None,
+            clear_instant_unstake_fee_recipient: None,
+        },
+    };
+
+    exec_with_actions(
+        &mut deps,
+        governance_env(),
+        mock_info(GOVERNANCE_TOKEN, &[]),
+        Addr::unchecked(PROPOSER1),
+        POLL_PROPOSAL_DEPOSIT,
+        POLL_TITLE.to_string(),
+        POLL_DESCRIPTION.to_string(),
+        None,
+        vec![mock_exec_msg(1)],
+        None,
+        None,
+        None,
+        Some(vec![action.clone()]),
+    ).unwrap();
+
+    let poll = Poll::load(&deps.storage, &1u64).unwrap();
+    assert_eq!(poll.actions, vec![action]);
+    assert_eq!(ExecutionMsg::from(&poll.executions[1]), ExecutionMsg::Execute {
+        order: 2,
+        contract: GOVERNANCE.to_string(),
+        msg: to_binary(&ExecuteMsg::UpdateStakingConfig {
+            distributor: None,
+            unstake_lock_period: Some(100),
+            instant_unstake_fee: None,
+            instant_unstake_fee_recipient: None,
+            clear_instant_unstake_fee_recipient: None,
+        }).unwrap(),
+        funds: vec![],
+    });
+}
+
+#[test]
+fn failed_gov_action_duplicated_order() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    let result = exec_with_actions(
+        &mut deps,
+        governance_env(),
+        mock_info(GOVERNANCE_TOKEN, &[]),
+        Addr::unchecked(PROPOSER1),
+        POLL_PROPOSAL_DEPOSIT,
+        POLL_TITLE.to_string(),
+        POLL_DESCRIPTION.to_string(),
+        None,
+        vec![mock_exec_msg(1)],
+        None,
+        None,
+        None,
+        Some(vec![GovActionMsg {
+            order: 1,
+            action: GovAction::UpdateStakingConfig {
+                distributor: None,
+                unstake_lock_period: Some(100),
+                instant_unstake_fee: None,
+                instant_unstake_fee_recipient: None,
+                clear_instant_unstake_fee_recipient: None,
+            },
+        }]),
+    );
+    expect_generic_err(&result, "Duplicated execution order: 1");
+}
+
+pub fn will_success_multiple_choice(
+    deps: &mut CustomDeps,
+    options: Vec<&str>,
+    rule: TallyRule,
+) -> (Env, MessageInfo, Response) {
+    let env = governance_env();
+    let info = mock_info(GOVERNANCE_TOKEN, &[]);
+
+    let response = exec_with_options(
+        deps,
+        env.clone(),
+        info.clone(),
+        Addr::unchecked(PROPOSER1),
+        POLL_PROPOSAL_DEPOSIT,
+        POLL_TITLE.to_string(),
+        POLL_DESCRIPTION.to_string(),
+        None,
+        vec![],
+        None,
+        Some(PollKind::MultipleChoice {
+            options: options.iter().map(|o| o.to_string()).collect(),
+            rule,
+        }),
+        None,
+    ).unwrap();
+
+    (env, info, response)
+}
+
+pub fn will_success_expedited(deps: &mut CustomDeps, deposit_amount: Uint128) -> (Env, MessageInfo, Response) {
+    let env = governance_env();
+    let info = mock_info(GOVERNANCE_TOKEN, &[]);
+
+    let response = exec_with_options(
+        deps,
+        env.clone(),
+        info.clone(),
+        Addr::unchecked(PROPOSER1),
+        deposit_amount,
+        POLL_TITLE.to_string(),
+        POLL_DESCRIPTION.to_string(),
+        None,
+        vec![mock_exec_msg(1)],
+        None,
+        None,
+        Some(true),
+    ).unwrap();
+
+    (env, info, response)
+}
+
+#[test]
+fn succeed_expedited() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    let (env, _, _) = will_success_expedited(&mut deps, POLL_EXPEDITED_PROPOSAL_DEPOSIT);
+
+    let poll = Poll::load(&deps.storage, &1).unwrap();
+    assert!(poll.expedited);
+    assert_eq!(poll.status, PollStatus::InProgress);
+    assert_eq!(poll.end_height, env.block.height + POLL_EXPEDITED_VOTING_PERIOD);
+
+    // A normal proposal deposit is not enough to start an expedited poll
+    will_success_expedited(&mut deps, POLL_PROPOSAL_DEPOSIT);
+
+    let poll = Poll::load(&deps.storage, &2).unwrap();
+    assert_eq!(poll.status, PollStatus::DepositPeriod);
+}
+
+#[test]
+fn succeed_multiple_choice() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    will_success_multiple_choice(&mut deps, vec!["Holdem", "Omaha", "Stud"], TallyRule::Plurality);
+
+    let poll = Poll::load(&deps.storage, &1).unwrap();
+    assert_eq!(poll.kind, PollKind::MultipleChoice {
+        options: vec!["Holdem".to_string(), "Omaha".to_string(), "Stud".to_string()],
+        rule: TallyRule::Plurality,
+    });
+    assert_eq!(poll.choice_votes, vec![Uint128::zero(); 3]);
+}
+
+#[test]
+fn failed_invalid_multiple_choice() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    let kind = |options: Vec<&str>| Some(PollKind::MultipleChoice {
+        options: options.iter().map(|o| o.to_string()).collect(),
+        rule: TallyRule::Approval,
+    });
+
+    let cases = vec![
+        (kind(vec!["Holdem"]), vec![], "Poll must have 2 to 10 options"),
+        (kind(vec!["A"; 11]), vec![], "Poll must have 2 to 10 options"),
+        (kind(vec!["Holdem", ""]), vec![], "Invalid poll option length"),
+        (kind(vec!["Holdem", "Holdem"]), vec![], "Duplicated poll option: Holdem"),
+        (kind(vec!["Holdem", "Omaha"]), vec![mock_exec_msg(1)], "Multiple choice poll can not have executions"),
+    ];
+
+    for (kind, executions, err) in cases {
+        let result = exec_with_options(
+            &mut deps,
+            governance_env(),
+            mock_info(GOVERNANCE_TOKEN, &[]),
+            Addr::unchecked(PROPOSER1),
+            POLL_PROPOSAL_DEPOSIT,
+            POLL_TITLE.to_string(),
+            POLL_DESCRIPTION.to_string(),
+            None,
+            executions,
+            None,
+            kind,
+            None,
+        );
+        expect_generic_err(&result, err);
+    }
+}
+
+#[test]
+fn failed_invalid_execution_order() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    let result = exec(
+        &mut deps,
+        governance_env(),
+        mock_info(GOVERNANCE_TOKEN, &[]),
+        Addr::unchecked(PROPOSER1),
+        POLL_PROPOSAL_DEPOSIT,
+        POLL_TITLE.to_string(),
+        POLL_DESCRIPTION.to_string(),
+        None,
+        vec![mock_exec_msg(1), mock_exec_msg(1)],
+    );
+    expect_generic_err(&result, "Duplicated execution order: 1");
+
+    let execution = ExecutionMsg::ClearAdmin {
+        order: u64::MAX,
+        contract: GOVERNANCE_TOKEN.to_string(),
+    };
+
+    let result = exec(
+        &mut deps,
+        governance_env(),
+        mock_info(GOVERNANCE_TOKEN, &[]),
+        Addr::unchecked(PROPOSER1),
+        POLL_PROPOSAL_DEPOSIT,
+        POLL_TITLE.to_string(),
+        POLL_DESCRIPTION.to_string(),
+        None,
+        vec![execution],
+    );
+    expect_generic_err(&result, "Execution order too large");
+}
+
+#[test]
+fn succeed_load_legacy_execution() {
+    let execution: Execution = from_slice(
+        br#"{"order":1,"contract":"terra1fmcjjt6yc9wqup2r06urnrd928jhrde6gcld6n","msg":"e30="}"#,
+    ).unwrap();
+
+    assert_eq!(ExecutionMsg::from(&execution), ExecutionMsg::Execute {
+        order: 1,
+        contract: "terra1fmcjjt6yc9wqup2r06urnrd928jhrde6gcld6n".to_string(),
+        msg: Binary::from(b"{}"),
+        funds: vec![],
+    });
+
+    let reloaded: Execution = from_slice(&to_vec(&execution).unwrap()).unwrap();
+    assert_eq!(ExecutionMsg::from(&reloaded), ExecutionMsg::from(&execution));
 }
 
 pub fn mock_exec_msg(order: u64) -> ExecutionMsg {
@@ -384,11 +905,12 @@ pub fn mock_exec_msg(order: u64) -> ExecutionMsg {
         "terra14mtctaszgzm4gcedlfslds802fmklnp4up72da",
     ];
 
-    ExecutionMsg {
+    ExecutionMsg::Execute {
         order,
         contract: contracts[order as usize].to_string(),
         msg: to_binary(&Cw20ExecuteMsg::Burn {
             amount: Uint128::new(1),
         }).unwrap(),
+        funds: vec![],
     }
 }
\ No newline at end of file
@@ -0,0 +1,183 @@
+use cosmwasm_std::{Addr, attr, CosmosMsg, Env, MessageInfo, Response, SubMsg, to_binary, Uint128, WasmMsg};
+use cosmwasm_std::testing::mock_info;
+use cw20::Cw20ExecuteMsg;
+
+use terrapoker::common::ContractResult;
+use terrapoker::governance::enumerations::PollStatus;
+use terrapoker::mock_querier::{custom_deps, CustomDeps};
+use terrapoker::test_constants::governance::*;
+use terrapoker::test_utils::{expect_generic_err, expect_unauthorized_err};
+
+use crate::poll::executions::deposit;
+use crate::poll::states::{Poll, PollState};
+use crate::poll::tests::cast_vote::VOTER1;
+use crate::poll::tests::create_poll::{POLL_DESCRIPTION, POLL_TITLE, PROPOSER1};
+use crate::tests::{init_default, POLL_DEPOSIT_PERIOD};
+
+pub fn exec(
+    deps: &mut CustomDeps,
+    env: Env,
+    info: MessageInfo,
+    depositor: &str,
+    amount: Uint128,
+    poll_id: u64,
+) -> ContractResult<Response> {
+    deps.querier.plus_token_balances(&[(
+        GOVERNANCE_TOKEN,
+        &[(GOVERNANCE, &amount)],
+    )]);
+
+    deposit(deps.as_mut(), env, info, Addr::unchecked(depositor), amount, poll_id)
+}
+
+pub fn will_success(
+    deps: &mut CustomDeps,
+    depositor: &str,
+    amount: Uint128,
+    poll_id: u64,
+) -> (Env, MessageInfo, Response) {
+    let env = governance_env();
+    let info = mock_info(GOVERNANCE_TOKEN, &[]);
+
+    let response = exec(deps, env.clone(), info.clone(), depositor, amount, poll_id).unwrap();
+
+    (env, info, response)
+}
+
+fn create_underfunded_poll(deps: &mut CustomDeps, deposit_amount: Uint128) {
+    super::create_poll::will_success(
+        deps,
+        PROPOSER1,
+        deposit_amount,
+        POLL_TITLE,
+        POLL_DESCRIPTION,
+        None,
+        vec![],
+    );
+}
+
+#[test]
+fn succeed() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    let proposer_deposit = POLL_PROPOSAL_DEPOSIT.multiply_ratio(1u128, 2u128);
+    let depositor_deposit = POLL_PROPOSAL_DEPOSIT.checked_sub(proposer_deposit).unwrap();
+
+    create_underfunded_poll(&mut deps, proposer_deposit);
+
+    let poll_id = 1u64;
+
+    let (env, _, response) = will_success(&mut deps, VOTER1, depositor_deposit, poll_id);
+    assert_eq!(response.attributes, vec![
+        attr("action", "deposit"),
+        attr("poll_id", poll_id.to_string()),
+        attr("depositor", VOTER1),
+        attr("amount", depositor_deposit.to_string()),
+        attr("deposit_amount", POLL_PROPOSAL_DEPOSIT.to_string()),
+        attr("poll_status", PollStatus::InProgress.to_string()),
+    ]);
+
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    assert_eq!(poll.status, PollStatus::InProgress);
+    assert_eq!(poll.start_height, env.block.height);
+    assert_eq!(poll.end_height, env.block.height + POLL_VOTING_PERIOD);
+    assert_eq!(poll.deposit_amount, POLL_PROPOSAL_DEPOSIT);
+    assert_eq!(poll.load_deposits(&deps.storage).unwrap(), vec![
+        (Addr::unchecked(PROPOSER1), proposer_deposit),
+        (Addr::unchecked(VOTER1), depositor_deposit),
+    ]);
+
+    let poll_state = PollState::load(&deps.storage).unwrap();
+    assert_eq!(poll_state.total_deposit, POLL_PROPOSAL_DEPOSIT);
+}
+
+#[test]
+fn succeed_drop_expired_poll() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    let proposer_deposit = Uint128::new(10);
+    let depositor_deposit = Uint128::new(20);
+
+    create_underfunded_poll(&mut deps, proposer_deposit);
+
+    let poll_id = 1u64;
+
+    will_success(&mut deps, VOTER1, depositor_deposit, poll_id);
+
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    let response = super::end_poll::exec(
+        &mut deps,
+        governance_env_height(poll.deposit_end_height + 1),
+        mock_info(PROPOSER1, &[]),
+        poll_id,
+    ).unwrap();
+
+    assert_eq!(response.messages, vec![
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: GOVERNANCE_TOKEN.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: PROPOSER1.to_string(),
+                amount: proposer_deposit,
+            }).unwrap(),
+        })),
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: GOVERNANCE_TOKEN.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: VOTER1.to_string(),
+                amount: depositor_deposit,
+            }).unwrap(),
+        })),
+    ]);
+
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    assert_eq!(poll.status, PollStatus::Dropped);
+
+    let poll_state = PollState::load(&deps.storage).unwrap();
+    assert_eq!(poll_state.total_deposit, Uint128::zero());
+}
+
+#[test]
+fn failed_after_deposit_period() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    create_underfunded_poll(&mut deps, Uint128::new(10));
+
+    let result = exec(
+        &mut deps,
+        governance_env_height(governance_env().block.height + POLL_DEPOSIT_PERIOD + 1),
+        mock_info(GOVERNANCE_TOKEN, &[]),
+        VOTER1,
+        POLL_PROPOSAL_DEPOSIT,
+        1,
+    );
+
+    expect_generic_err(&result, "Poll is not in deposit period");
+}
+
+#[test]
+fn failed_invalid_permission() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    create_underfunded_poll(&mut deps, Uint128::new(10));
+
+    let result = exec(
+        &mut deps,
+        governance_env(),
+        mock_info(VOTER1, &[]),
+        VOTER1,
+        POLL_PROPOSAL_DEPOSIT,
+        1,
+    );
+
+    expect_unauthorized_err(&result);
+}
@@ -1,12 +1,14 @@
 use terrapoker::mock_querier::{CustomDeps, custom_deps};
-use cosmwasm_std::{Env, MessageInfo, Response, CosmosMsg, WasmMsg, Uint128, attr, to_binary, SubMsg};
+use cosmwasm_std::{Addr, Decimal, Env, MessageInfo, Response, CosmosMsg, WasmMsg, Uint128, attr, to_binary, SubMsg};
 use terrapoker::common::ContractResult;
 use crate::poll::executions::end_poll;
-use crate::tests::init_default;
+use crate::poll::queries::{query_poll_tally, query_voters};
+use crate::staking::queries::query_staker_votes;
+use crate::tests::{init_default, POLL_EXPEDITED_PROPOSAL_DEPOSIT};
 use cw20::Cw20ExecuteMsg;
 use cosmwasm_std::testing::mock_info;
-use crate::poll::states::{Poll, PollResult};
-use terrapoker::governance::enumerations::{PollStatus, VoteOption};
+use crate::poll::states::{Poll, PollConfig, PollResult};
+use terrapoker::governance::enumerations::{DepositOutcome, PollStatus, TallyRule, VoteOption};
 use crate::poll::tests::cast_vote::{VOTER1, VOTER2, VOTER3};
 use terrapoker::message_matchers;
 use crate::poll::tests::create_poll::PROPOSER1;
@@ -14,6 +16,8 @@ use terrapoker::test_utils::expect_generic_err;
 use terrapoker::test_constants::default_sender;
 use terrapoker::test_constants::governance::*;
 
+pub const VETO_DEPOSIT_RECIPIENT: &str = "terra1dcegyrekltswvyy0xy69ydgxn9x8x32zdtapd8";
+
 pub fn exec(deps: &mut CustomDeps, env: Env, info: MessageInfo, poll_id: u64) -> ContractResult<Response> {
     let response = end_poll(deps.as_mut(), env, info, poll_id)?;
 
@@ -122,6 +126,77 @@ fn succeed_rejected_threshold_not_reached() {
     assert_eq!(poll.status, PollStatus::Rejected);
 }
 
+#[test]
+fn succeed_rejected_vetoed() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    let staker1_staked_amount = Uint128::new(100);
+    let staker2_staked_amount = Uint128::new(100);
+    let staker3_staked_amount = Uint128::new(100);
+
+    super::create_poll::default(&mut deps);
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER1, staker1_staked_amount);
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER2, staker2_staked_amount);
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER3, staker3_staked_amount);
+
+    let poll_id = 1u64;
+
+    super::cast_vote::will_success(&mut deps, VOTER1, poll_id, VoteOption::Yes, Uint128::new(100));
+    super::cast_vote::will_success(&mut deps, VOTER2, poll_id, VoteOption::NoWithVeto, Uint128::new(100));
+
+    let (_, _, response) = will_success(&mut deps, poll_id);
+    assert_eq!(response.messages, vec![
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: GOVERNANCE_TOKEN.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Burn {
+                amount: POLL_PROPOSAL_DEPOSIT,
+            }).unwrap(),
+        })),
+    ]);
+    assert_eq!(response.attributes, vec![
+        attr("action", "end_poll"),
+        attr("poll_id", poll_id.to_string()),
+        attr("result", PollResult::Vetoed.to_string()),
+        attr("passed", "false"),
+    ]);
+
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    assert_eq!(poll.status, PollStatus::Rejected);
+}
+
+#[test]
+fn succeed_rejected_vetoed_with_recipient() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    let mut poll_config = PollConfig::load(&deps.storage).unwrap();
+    poll_config.veto_deposit_recipient = Some(Addr::unchecked(VETO_DEPOSIT_RECIPIENT));
+    poll_config.save(&mut deps.storage).unwrap();
+
+    super::create_poll::default(&mut deps);
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER1, Uint128::new(100));
+
+    let poll_id = 1u64;
+
+    super::cast_vote::will_success(&mut deps, VOTER1, poll_id, VoteOption::NoWithVeto, Uint128::new(100));
+
+    let (_, _, response) = will_success(&mut deps, poll_id);
+    assert_eq!(response.messages, vec![
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: GOVERNANCE_TOKEN.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: VETO_DEPOSIT_RECIPIENT.to_string(),
+                amount: POLL_PROPOSAL_DEPOSIT,
+            }).unwrap(),
+        })),
+    ]);
+}
+
 #[test]
 fn succeed_rejected_quorum_not_reached() {
     let mut deps = custom_deps();
@@ -231,3 +306,277 @@ fn failed_before_end_height() {
 
     expect_generic_err(&result, "Voting period has not expired");
 }
+
+#[test]
+fn succeed_multiple_choice() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    super::create_poll::will_success_multiple_choice(&mut deps, vec!["Holdem", "Omaha", "Stud"], TallyRule::Plurality);
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER1, Uint128::new(100));
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER2, Uint128::new(100));
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER3, Uint128::new(100));
+
+    let poll_id = 1u64;
+
+    super::cast_vote::will_success(&mut deps, VOTER1, poll_id, VoteOption::Choice(1), Uint128::new(100));
+    super::cast_vote::will_success(&mut deps, VOTER2, poll_id, VoteOption::Choice(1), Uint128::new(50));
+    super::cast_vote::will_success(&mut deps, VOTER3, poll_id, VoteOption::Choice(2), Uint128::new(100));
+
+    let (_, _, response) = will_success(&mut deps, poll_id);
+    assert!(response.attributes.contains(&attr("winning_choice", "1")));
+
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    assert_eq!(poll.status, PollStatus::Passed);
+    assert_eq!(poll.winning_choice, Some(1));
+}
+
+#[test]
+fn succeed_multiple_choice_tie() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    super::create_poll::will_success_multiple_choice(&mut deps, vec!["Holdem", "Omaha"], TallyRule::Plurality);
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER1, Uint128::new(100));
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER2, Uint128::new(100));
+
+    let poll_id = 1u64;
+
+    super::cast_vote::will_success(&mut deps, VOTER1, poll_id, VoteOption::Choice(0), Uint128::new(100));
+    super::cast_vote::will_success(&mut deps, VOTER2, poll_id, VoteOption::Choice(1), Uint128::new(100));
+
+    let (_, _, response) = will_success(&mut deps, poll_id);
+    assert!(response.attributes.contains(&attr("result", PollResult::Tie.to_string())));
+
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    assert_eq!(poll.status, PollStatus::Rejected);
+    assert_eq!(poll.winning_choice, None);
+}
+
+#[test]
+fn succeed_expedited_passed() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    super::create_poll::will_success_expedited(&mut deps, POLL_EXPEDITED_PROPOSAL_DEPOSIT);
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER1, Uint128::new(100));
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER2, Uint128::new(100));
+
+    let poll_id = 1u64;
+
+    super::cast_vote::will_success(&mut deps, VOTER1, poll_id, VoteOption::Yes, Uint128::new(100));
+    super::cast_vote::will_success(&mut deps, VOTER2, poll_id, VoteOption::No, Uint128::new(20));
+
+    will_success(&mut deps, poll_id);
+
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    assert_eq!(poll.status, PollStatus::Passed);
+}
+
+#[test]
+fn succeed_expedited_fallback() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    super::create_poll::will_success_expedited(&mut deps, POLL_EXPEDITED_PROPOSAL_DEPOSIT);
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER1, Uint128::new(100));
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER2, Uint128::new(100));
+
+    let poll_id = 1u64;
+
+    // 60% yes passes the normal threshold but not the expedited one
+    super::cast_vote::will_success(&mut deps, VOTER1, poll_id, VoteOption::Yes, Uint128::new(60));
+    super::cast_vote::will_success(&mut deps, VOTER2, poll_id, VoteOption::No, Uint128::new(40));
+
+    let (_, _, response) = will_success(&mut deps, poll_id);
+    assert!(response.messages.is_empty());
+    assert!(response.attributes.contains(&attr("expedited_fallback", "true")));
+
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    assert_eq!(poll.status, PollStatus::InProgress);
+    assert!(!poll.expedited);
+    assert_eq!(poll.end_height, poll.start_height + POLL_VOTING_PERIOD);
+
+    will_success(&mut deps, poll_id);
+
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    assert_eq!(poll.status, PollStatus::Passed);
+}
+
+pub fn enable_early_finalization(deps: &mut CustomDeps) {
+    let mut poll_config = PollConfig::load(&deps.storage).unwrap();
+    poll_config.early_finalization = true;
+    poll_config.save(&mut deps.storage).unwrap();
+}
+
+#[test]
+fn succeed_finalized_early() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+    enable_early_finalization(&mut deps);
+
+    super::create_poll::default(&mut deps);
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER1, Uint128::new(100));
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER2, Uint128::new(100));
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER3, Uint128::new(50));
+
+    let poll_id = 1u64;
+
+    let mut poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    poll.total_balance_at_start_poll = Uint128::new(250);
+    poll.save(&mut deps.storage).unwrap();
+
+    super::cast_vote::will_success(&mut deps, VOTER1, poll_id, VoteOption::Yes, Uint128::new(100));
+    super::cast_vote::will_success(&mut deps, VOTER2, poll_id, VoteOption::Yes, Uint128::new(100));
+
+    let env = governance_env();
+    let response = exec(&mut deps, env.clone(), default_sender(), poll_id).unwrap();
+    assert!(response.attributes.contains(&attr("result", PollResult::Passed.to_string())));
+    assert!(response.attributes.contains(&attr("finalized_early", "true")));
+    assert!(response.attributes.contains(&attr("end_height", env.block.height.to_string())));
+
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    assert_eq!(poll.status, PollStatus::Passed);
+    assert!(poll.finalized_early);
+    assert_eq!(poll.end_height, env.block.height);
+}
+
+#[test]
+fn failed_finalize_early_not_decided() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+    enable_early_finalization(&mut deps);
+
+    super::create_poll::default(&mut deps);
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER1, Uint128::new(100));
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER2, Uint128::new(100));
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER3, Uint128::new(100));
+
+    let poll_id = 1u64;
+
+    let mut poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    poll.total_balance_at_start_poll = Uint128::new(300);
+    poll.save(&mut deps.storage).unwrap();
+
+    // The remaining 150 can still vote no and bring yes down to 50%
+    super::cast_vote::will_success(&mut deps, VOTER1, poll_id, VoteOption::Yes, Uint128::new(100));
+    super::cast_vote::will_success(&mut deps, VOTER2, poll_id, VoteOption::Yes, Uint128::new(50));
+
+    let result = exec(&mut deps, governance_env(), default_sender(), poll_id);
+    expect_generic_err(&result, "Voting period has not expired");
+}
+
+#[test]
+fn succeed_revote_flips_result() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    super::create_poll::default(&mut deps);
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER1, Uint128::new(100));
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER2, Uint128::new(100));
+
+    let poll_id = 1u64;
+
+    let mut poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    poll.total_balance_at_start_poll = Uint128::new(200);
+    poll.save(&mut deps.storage).unwrap();
+
+    // Every vote is cast yes, but both voters change their mind before the end
+    super::cast_vote::will_success(&mut deps, VOTER1, poll_id, VoteOption::Yes, Uint128::new(100));
+    super::cast_vote::will_success(&mut deps, VOTER2, poll_id, VoteOption::Yes, Uint128::new(100));
+    super::cast_vote::will_success(&mut deps, VOTER1, poll_id, VoteOption::No, Uint128::new(100));
+    super::cast_vote::will_success(&mut deps, VOTER2, poll_id, VoteOption::No, Uint128::new(100));
+
+    let (_, _, response) = will_success(&mut deps, poll_id);
+    assert!(response.attributes.contains(&attr("result", PollResult::ThresholdNotReached.to_string())));
+}
+
+#[test]
+fn failed_revote_with_early_finalization() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+    enable_early_finalization(&mut deps);
+
+    super::create_poll::default(&mut deps);
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER1, Uint128::new(100));
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER2, Uint128::new(100));
+
+    let poll_id = 1u64;
+
+    // The poll is decided once every vote is cast yes, so the votes can not flip it anymore
+    super::cast_vote::will_success(&mut deps, VOTER1, poll_id, VoteOption::Yes, Uint128::new(100));
+    super::cast_vote::will_success(&mut deps, VOTER2, poll_id, VoteOption::Yes, Uint128::new(100));
+
+    let result = super::cast_vote::exec(
+        &mut deps,
+        governance_env(),
+        mock_info(VOTER1, &[]),
+        poll_id,
+        VoteOption::No,
+        Uint128::new(100),
+    );
+    expect_generic_err(&result, "Votes can not be changed while early finalization is enabled");
+
+    let result = super::retract_vote::exec(&mut deps, governance_env(), mock_info(VOTER2, &[]), poll_id);
+    expect_generic_err(&result, "Votes can not be changed while early finalization is enabled");
+}
+
+#[test]
+fn succeed_tally_matches_result() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    super::create_poll::default(&mut deps);
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER1, Uint128::new(100));
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER2, Uint128::new(100));
+
+    let poll_id = 1u64;
+
+    super::cast_vote::will_success(&mut deps, VOTER1, poll_id, VoteOption::Yes, Uint128::new(100));
+    super::cast_vote::will_success(&mut deps, VOTER2, poll_id, VoteOption::NoWithVeto, Uint128::new(100));
+
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    let env = governance_env_height(poll.end_height + 1);
+
+    let tally = query_poll_tally(deps.as_ref(), env.clone(), poll_id).unwrap();
+    assert_eq!(tally.result, PollResult::Vetoed.to_string());
+    assert_eq!(tally.yes_ratio, Decimal::percent(50));
+    assert_eq!(tally.blocks_remaining, 0);
+    assert!(tally.can_end);
+    assert_eq!(tally.deposit_outcome, DepositOutcome::Confiscated);
+
+    let response = exec(&mut deps, env, default_sender(), poll_id).unwrap();
+    assert!(response.attributes.contains(&attr("result", tally.result)));
+}
+
+#[test]
+fn succeed_voters_kept_after_end() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    super::create_poll::default(&mut deps);
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER1, Uint128::new(100));
+
+    let poll_id = 1u64;
+
+    super::cast_vote::will_success(&mut deps, VOTER1, poll_id, VoteOption::Yes, Uint128::new(100));
+
+    let (env, _, _) = will_success(&mut deps, poll_id);
+
+    let voters = query_voters(deps.as_ref(), env.clone(), poll_id, None, None, None).unwrap();
+    assert_eq!(voters.voters.len(), 1);
+    assert_eq!(voters.voters[0].voter, VOTER1.to_string());
+
+    let history = query_staker_votes(deps.as_ref(), env, VOTER1.to_string(), None, None).unwrap();
+    assert_eq!(history.votes, vec![(poll_id, voters.voters[0].clone())]);
+}
@@ -1,6 +1,7 @@
-use cosmwasm_std::{CosmosMsg, Env, MessageInfo, ReplyOn, Response, SubMsg, to_binary, Uint128, WasmMsg};
+use cosmwasm_std::{Addr, CosmosMsg, Env, MessageInfo, ReplyOn, Response, SubMsg, to_binary, Uint128, WasmMsg};
+use cosmwasm_std::testing::mock_info;
 
-use terrapoker::common::ContractResult;
+use terrapoker::common::{ContractResult, Execution};
 use terrapoker::governance::enumerations::VoteOption;
 use terrapoker::governance::execute_msgs::ExecuteMsg;
 use terrapoker::mock_querier::{custom_deps, CustomDeps};
@@ -8,11 +9,11 @@ use terrapoker::test_constants::default_sender;
 use terrapoker::test_constants::governance::*;
 use terrapoker::test_utils::expect_generic_err;
 
-use crate::poll::executions::{execute_poll, REPLY_EXECUTION};
+use crate::poll::executions::{execute_poll, REPLY_EXECUTION, REPLY_EXECUTION_RESULT};
 use crate::poll::states::{Poll, PollExecutionContext};
 use crate::poll::tests::cast_vote::VOTER1;
 use crate::poll::tests::create_poll::{mock_exec_msg, POLL_DESCRIPTION, POLL_LINK, POLL_TITLE, PROPOSER1};
-use crate::tests::init_default;
+use crate::tests::{init_default, POLL_EXECUTION_EXPIRY_PERIOD};
 
 pub fn exec(deps: &mut CustomDeps, env: Env, info: MessageInfo, poll_id: u64) -> ContractResult<Response> {
     execute_poll(deps.as_mut(), env, info, poll_id)
@@ -84,6 +85,47 @@ fn succeed() {
     });
 }
 
+#[test]
+fn succeed_best_effort() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    let execution_msgs = vec![
+        mock_exec_msg(2),
+        mock_exec_msg(1),
+    ];
+
+    super::create_poll::exec_with_options(
+        &mut deps,
+        governance_env(),
+        mock_info(GOVERNANCE_TOKEN, &[]),
+        Addr::unchecked(PROPOSER1),
+        POLL_PROPOSAL_DEPOSIT,
+        POLL_TITLE.to_string(),
+        POLL_DESCRIPTION.to_string(),
+        None,
+        execution_msgs.clone(),
+        Some(false),
+        None,
+        None,
+    ).unwrap();
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER1, Uint128::new(100));
+
+    let poll_id = 1u64;
+
+    super::cast_vote::will_success(&mut deps, VOTER1, poll_id, VoteOption::Yes, Uint128::new(100));
+    super::end_poll::will_success(&mut deps, poll_id);
+
+    let (_, _, response) = will_success(&mut deps, poll_id);
+    assert_eq!(response.messages, vec![mock_exec_msg(1), mock_exec_msg(2)].iter().map(|e| SubMsg {
+        id: REPLY_EXECUTION_RESULT + e.order(),
+        msg: Execution::from(&deps.api, e).unwrap().to_cosmos_msg(),
+        gas_limit: None,
+        reply_on: ReplyOn::Always,
+    }).collect::<Vec<SubMsg>>());
+}
+
 #[test]
 fn failed_not_passed() {
     let mut deps = custom_deps();
@@ -163,6 +205,88 @@ fn failed_in_execution_delay() {
     expect_generic_err(&result, "Execution delay period has not expired");
 }
 
+#[test]
+fn succeed_finalized_early() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+    super::end_poll::enable_early_finalization(&mut deps);
+
+    super::create_poll::will_success(
+        &mut deps,
+        PROPOSER1,
+        POLL_PROPOSAL_DEPOSIT,
+        POLL_TITLE,
+        POLL_DESCRIPTION,
+        Some(POLL_LINK),
+        vec![mock_exec_msg(1)],
+    );
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER1, Uint128::new(100));
+
+    let poll_id = 1u64;
+
+    let mut poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    poll.total_balance_at_start_poll = Uint128::new(100);
+    poll.save(&mut deps.storage).unwrap();
+    let voting_end_height = poll.end_height;
+
+    super::cast_vote::will_success(&mut deps, VOTER1, poll_id, VoteOption::Yes, Uint128::new(100));
+
+    let finalized_height = governance_env().block.height;
+    super::end_poll::exec(&mut deps, governance_env(), default_sender(), poll_id).unwrap();
+    assert!(finalized_height < voting_end_height);
+
+    let result = exec(
+        &mut deps,
+        governance_env_height(finalized_height + POLL_EXECUTION_DELAY_PERIOD - 1),
+        default_sender(),
+        poll_id,
+    );
+    expect_generic_err(&result, "Execution delay period has not expired");
+
+    exec(
+        &mut deps,
+        governance_env_height(finalized_height + POLL_EXECUTION_DELAY_PERIOD),
+        default_sender(),
+        poll_id,
+    ).unwrap();
+}
+
+#[test]
+fn failed_expired() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    super::create_poll::will_success(
+        &mut deps,
+        PROPOSER1,
+        POLL_PROPOSAL_DEPOSIT,
+        POLL_TITLE,
+        POLL_DESCRIPTION,
+        Some(POLL_LINK),
+        vec![mock_exec_msg(1)],
+    );
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER1, Uint128::new(100));
+
+    let poll_id = 1u64;
+
+    super::cast_vote::will_success(&mut deps, VOTER1, poll_id, VoteOption::Yes, Uint128::new(100));
+    super::end_poll::will_success(&mut deps, poll_id);
+
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    let env = governance_env_height(poll.end_height + POLL_EXECUTION_DELAY_PERIOD + POLL_EXECUTION_EXPIRY_PERIOD + 1);
+
+    let result = exec(
+        &mut deps,
+        env,
+        default_sender(),
+        poll_id,
+    );
+
+    expect_generic_err(&result, "Execution period has expired");
+}
+
 #[test]
 fn failed_empty_execution() {
     let mut deps = custom_deps();
@@ -0,0 +1,99 @@
+use cosmwasm_std::{attr, Env, MessageInfo, Response, Uint128};
+
+use terrapoker::common::ContractResult;
+use terrapoker::governance::enumerations::{PollStatus, VoteOption};
+use terrapoker::mock_querier::{custom_deps, CustomDeps};
+use terrapoker::test_constants::default_sender;
+use terrapoker::test_constants::governance::*;
+use terrapoker::test_utils::expect_generic_err;
+
+use crate::poll::executions::expire_poll;
+use crate::poll::states::Poll;
+use crate::poll::tests::cast_vote::VOTER1;
+use crate::poll::tests::create_poll::{mock_exec_msg, POLL_DESCRIPTION, POLL_LINK, POLL_TITLE, PROPOSER1};
+use crate::tests::{init_default, POLL_EXECUTION_EXPIRY_PERIOD};
+
+pub fn exec(deps: &mut CustomDeps, env: Env, info: MessageInfo, poll_id: u64) -> ContractResult<Response> {
+    expire_poll(deps.as_mut(), env, info, poll_id)
+}
+
+pub fn will_success(deps: &mut CustomDeps, poll_id: u64) -> (Env, MessageInfo, Response) {
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    let env = governance_env_height(poll.end_height + POLL_EXECUTION_DELAY_PERIOD + POLL_EXECUTION_EXPIRY_PERIOD + 1);
+
+    let info = default_sender();
+
+    let response = exec(deps, env.clone(), info.clone(), poll_id).unwrap();
+
+    (env, info, response)
+}
+
+fn pass_default(deps: &mut CustomDeps, vote_option: VoteOption) -> u64 {
+    init_default(deps.as_mut());
+
+    super::create_poll::will_success(
+        deps,
+        PROPOSER1,
+        POLL_PROPOSAL_DEPOSIT,
+        POLL_TITLE,
+        POLL_DESCRIPTION,
+        Some(POLL_LINK),
+        vec![mock_exec_msg(1)],
+    );
+    crate::staking::tests::stake_token_hook::will_success(deps, VOTER1, Uint128::new(100));
+
+    let poll_id = 1u64;
+
+    super::cast_vote::will_success(deps, VOTER1, poll_id, vote_option, Uint128::new(100));
+    super::end_poll::will_success(deps, poll_id);
+
+    poll_id
+}
+
+#[test]
+fn succeed() {
+    let mut deps = custom_deps();
+
+    let poll_id = pass_default(&mut deps, VoteOption::Yes);
+
+    let (_, _, response) = will_success(&mut deps, poll_id);
+    assert_eq!(response.attributes, vec![
+        attr("action", "expire_poll"),
+        attr("poll_id", poll_id.to_string()),
+    ]);
+
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    assert_eq!(poll.status, PollStatus::Expired);
+
+    let polls = Poll::query(&deps.storage, Some(PollStatus::Expired), None, None, None).unwrap();
+    assert_eq!(polls.len(), 1);
+
+    let polls = Poll::query(&deps.storage, Some(PollStatus::Passed), None, None, None).unwrap();
+    assert!(polls.is_empty());
+}
+
+#[test]
+fn failed_not_expired() {
+    let mut deps = custom_deps();
+
+    let poll_id = pass_default(&mut deps, VoteOption::Yes);
+
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    let env = governance_env_height(poll.end_height + POLL_EXECUTION_DELAY_PERIOD + POLL_EXECUTION_EXPIRY_PERIOD);
+
+    let result = exec(&mut deps, env, default_sender(), poll_id);
+    expect_generic_err(&result, "Execution period has not expired");
+}
+
+#[test]
+fn failed_not_passed() {
+    let mut deps = custom_deps();
+
+    let poll_id = pass_default(&mut deps, VoteOption::No);
+
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    let env = governance_env_height(poll.end_height + POLL_EXECUTION_DELAY_PERIOD + POLL_EXECUTION_EXPIRY_PERIOD + 1);
+
+    let result = exec(&mut deps, env, default_sender(), poll_id);
+    expect_generic_err(&result, "Poll is not in passed status");
+}
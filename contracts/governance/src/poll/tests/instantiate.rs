@@ -1,14 +1,21 @@
-use cosmwasm_std::{Decimal, Env, MessageInfo, Response, Uint128};
+use cosmwasm_std::{Addr, Decimal, Env, MessageInfo, Response, Storage, to_binary, Uint128};
+use cw20::Cw20ExecuteMsg;
 
-use terrapoker::common::ContractResult;
+use terrapoker::common::{ContractResult, Execution};
 use terrapoker::governance::execute_msgs::PollConfigInitMsg;
+use terrapoker::governance::models::ExecutionPolicyMsg;
 use terrapoker::mock_querier::{custom_deps, CustomDeps};
 use terrapoker::test_constants::default_sender;
 use terrapoker::test_constants::governance::*;
 use terrapoker::test_utils::expect_generic_err;
 
 use crate::poll::executions::instantiate;
-use crate::poll::states::{PollConfig, PollState};
+use crate::poll::states::{PollConfig, PollState, QuorumBase};
+use crate::tests::{
+    default_execution_policy, default_quorum_base, POLL_CANCEL_SLASH_PERCENT, POLL_DEPOSIT_PERIOD, POLL_EXECUTION_EXPIRY_PERIOD,
+    POLL_EARLY_FINALIZATION, POLL_EXPEDITED_PROPOSAL_DEPOSIT, POLL_EXPEDITED_THRESHOLD_PERCENT, POLL_EXPEDITED_VOTING_PERIOD, POLL_VETO_THRESHOLD_PERCENT,
+    POLL_MAX_POLLS_PER_PROPOSER, POLL_MIN_PROPOSER_BALANCE, POLL_PROPOSAL_COOLDOWN_PERIOD,
+};
 
 pub fn exec(
     deps: &mut CustomDeps,
@@ -20,12 +27,29 @@ pub fn exec(
     execution_delay_period: u64,
     proposal_deposit: Uint128,
 ) -> ContractResult<Response> {
+    crate::common::tests::instantiate::default(deps);
+
     let msg = PollConfigInitMsg {
         quorum,
         threshold,
         voting_period,
         execution_delay_period,
         proposal_deposit,
+        deposit_period: POLL_DEPOSIT_PERIOD,
+        veto_threshold: Decimal::percent(POLL_VETO_THRESHOLD_PERCENT),
+        veto_deposit_recipient: None,
+        cancel_slash_rate: Decimal::percent(POLL_CANCEL_SLASH_PERCENT),
+        execution_expiry_period: POLL_EXECUTION_EXPIRY_PERIOD,
+        execution_policy: default_execution_policy(),
+        expedited_voting_period: POLL_EXPEDITED_VOTING_PERIOD,
+        expedited_threshold: Decimal::percent(POLL_EXPEDITED_THRESHOLD_PERCENT),
+        expedited_proposal_deposit: POLL_EXPEDITED_PROPOSAL_DEPOSIT,
+        early_finalization: POLL_EARLY_FINALIZATION,
+        min_proposer_balance: POLL_MIN_PROPOSER_BALANCE,
+        max_polls_per_proposer: POLL_MAX_POLLS_PER_PROPOSER,
+        proposal_cooldown_period: POLL_PROPOSAL_COOLDOWN_PERIOD,
+        quorum_base: default_quorum_base(),
+        guardian: None,
     };
 
     instantiate(deps.as_mut(), env, info, msg)
@@ -62,6 +86,20 @@ fn succeed() {
     assert_eq!(poll_config.voting_period, POLL_VOTING_PERIOD);
     assert_eq!(poll_config.execution_delay_period, POLL_EXECUTION_DELAY_PERIOD);
     assert_eq!(poll_config.proposal_deposit, POLL_PROPOSAL_DEPOSIT);
+    assert_eq!(poll_config.deposit_period, POLL_DEPOSIT_PERIOD);
+    assert_eq!(poll_config.veto_threshold, Decimal::percent(POLL_VETO_THRESHOLD_PERCENT));
+    assert_eq!(poll_config.veto_deposit_recipient, None);
+    assert_eq!(poll_config.cancel_slash_rate, Decimal::percent(POLL_CANCEL_SLASH_PERCENT));
+    assert_eq!(poll_config.execution_expiry_period, POLL_EXECUTION_EXPIRY_PERIOD);
+    assert_eq!(poll_config.expedited_voting_period, POLL_EXPEDITED_VOTING_PERIOD);
+    assert_eq!(poll_config.expedited_threshold, Decimal::percent(POLL_EXPEDITED_THRESHOLD_PERCENT));
+    assert_eq!(poll_config.expedited_proposal_deposit, POLL_EXPEDITED_PROPOSAL_DEPOSIT);
+    assert_eq!(poll_config.early_finalization, POLL_EARLY_FINALIZATION);
+    assert_eq!(poll_config.min_proposer_balance, POLL_MIN_PROPOSER_BALANCE);
+    assert_eq!(poll_config.max_polls_per_proposer, POLL_MAX_POLLS_PER_PROPOSER);
+    assert_eq!(poll_config.proposal_cooldown_period, POLL_PROPOSAL_COOLDOWN_PERIOD);
+    assert_eq!(poll_config.quorum_base.to_msg(), default_quorum_base());
+    assert_eq!(poll_config.guardian, None);
 
     let poll_state = PollState::load(&deps.storage).unwrap();
     assert_eq!(poll_state.poll_count, 0);
@@ -120,4 +158,132 @@ fn failed_invalid_execution_delay_period() {
     );
 
     expect_generic_err(&result, "execution_delay_period must be greater than 1000");
-}
\ No newline at end of file
+}
+#[test]
+fn succeed_load_legacy_config() {
+    let mut deps = custom_deps();
+
+    deps.storage.set(
+        b"poll-config",
+        br#"{"quorum":"0.1","threshold":"0.5","voting_period":100,"execution_delay_period":1000,"proposal_deposit":"100"}"#,
+    );
+
+    let poll_config = PollConfig::load(&deps.storage).unwrap();
+    assert_eq!(poll_config.quorum, Decimal::percent(10));
+    assert_eq!(poll_config.proposal_deposit, Uint128::new(100));
+    assert_eq!(poll_config.deposit_period, 0);
+    assert_eq!(poll_config.veto_threshold, Decimal::one());
+    assert_eq!(poll_config.veto_deposit_recipient, None);
+    assert_eq!(poll_config.cancel_slash_rate, Decimal::zero());
+    assert_eq!(poll_config.execution_expiry_period, u64::MAX);
+    assert!(!poll_config.execution_policy.allow_any_target);
+    assert_eq!(poll_config.expedited_voting_period, 0);
+    assert!(!poll_config.early_finalization);
+    assert_eq!(poll_config.quorum_base, QuorumBase::TotalSupply {});
+    assert_eq!(poll_config.guardian, None);
+}
+
+#[test]
+fn succeed_deny_token_transfers_without_policy() {
+    let mut deps = custom_deps();
+
+    crate::common::tests::instantiate::default(&mut deps);
+
+    let msg = PollConfigInitMsg {
+        execution_policy: ExecutionPolicyMsg {
+            allow_any_target: true,
+            targets: vec![],
+        },
+        ..default_init_msg()
+    };
+    instantiate(deps.as_mut(), governance_env(), default_sender(), msg).unwrap();
+
+    let poll_config = PollConfig::load(&deps.storage).unwrap();
+    assert_token_transfers_denied(&poll_config);
+}
+
+#[test]
+fn succeed_migrate_legacy_policy() {
+    let mut deps = custom_deps();
+
+    crate::common::tests::instantiate::default(&mut deps);
+
+    deps.storage.set(
+        b"poll-config",
+        br#"{"quorum":"0.1","threshold":"0.5","voting_period":100,"execution_delay_period":1000,"proposal_deposit":"100"}"#,
+    );
+
+    crate::migrations::migrate(deps.as_mut(), &governance_env()).unwrap();
+
+    let poll_config = PollConfig::load(&deps.storage).unwrap();
+    assert!(poll_config.execution_policy.allow_any_target);
+    assert_token_transfers_denied(&poll_config);
+    assert_eq!(poll_config.expedited_proposal_deposit, Uint128::new(100));
+}
+
+#[test]
+fn failed_invalid_expedited_proposal_deposit() {
+    let mut deps = custom_deps();
+
+    crate::common::tests::instantiate::default(&mut deps);
+
+    let msg = PollConfigInitMsg {
+        expedited_proposal_deposit: POLL_PROPOSAL_DEPOSIT - Uint128::new(1),
+        ..default_init_msg()
+    };
+    let result = instantiate(deps.as_mut(), governance_env(), default_sender(), msg);
+
+    expect_generic_err(&result, "expedited_proposal_deposit must not be less than proposal_deposit");
+}
+
+fn default_init_msg() -> PollConfigInitMsg {
+    PollConfigInitMsg {
+        quorum: Decimal::percent(POLL_QUORUM_PERCENT),
+        threshold: Decimal::percent(POLL_THRESHOLD_PERCENT),
+        voting_period: POLL_VOTING_PERIOD,
+        execution_delay_period: POLL_EXECUTION_DELAY_PERIOD,
+        proposal_deposit: POLL_PROPOSAL_DEPOSIT,
+        deposit_period: POLL_DEPOSIT_PERIOD,
+        veto_threshold: Decimal::percent(POLL_VETO_THRESHOLD_PERCENT),
+        veto_deposit_recipient: None,
+        cancel_slash_rate: Decimal::percent(POLL_CANCEL_SLASH_PERCENT),
+        execution_expiry_period: POLL_EXECUTION_EXPIRY_PERIOD,
+        execution_policy: default_execution_policy(),
+        expedited_voting_period: POLL_EXPEDITED_VOTING_PERIOD,
+        expedited_threshold: Decimal::percent(POLL_EXPEDITED_THRESHOLD_PERCENT),
+        expedited_proposal_deposit: POLL_EXPEDITED_PROPOSAL_DEPOSIT,
+        early_finalization: POLL_EARLY_FINALIZATION,
+        min_proposer_balance: POLL_MIN_PROPOSER_BALANCE,
+        max_polls_per_proposer: POLL_MAX_POLLS_PER_PROPOSER,
+        proposal_cooldown_period: POLL_PROPOSAL_COOLDOWN_PERIOD,
+        quorum_base: default_quorum_base(),
+        guardian: None,
+    }
+}
+
+fn assert_token_transfers_denied(poll_config: &PollConfig) {
+    for token in [STAKING_TOKEN, GOVERNANCE_TOKEN] {
+        let execution = Execution::Execute {
+            order: 1,
+            contract: Addr::unchecked(token),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: default_sender().sender.to_string(),
+                amount: Uint128::new(1),
+            }).unwrap(),
+            funds: vec![],
+        };
+        assert!(poll_config.execution_policy.validate(&execution).is_err());
+
+        let execution = Execution::Execute {
+            order: 1,
+            contract: Addr::unchecked(token),
+            msg: to_binary(&Cw20ExecuteMsg::IncreaseAllowance {
+                spender: default_sender().sender.to_string(),
+                amount: Uint128::new(1),
+                expires: None,
+            }).unwrap(),
+            funds: vec![],
+        };
+        assert!(poll_config.execution_policy.validate(&execution).is_err());
+    }
+}
@@ -1,8 +1,15 @@
 pub mod instantiate;
 pub mod update_config;
 pub mod create_poll;
+pub mod deposit;
 pub mod cast_vote;
+pub mod retract_vote;
+pub mod cancel_poll;
 pub mod end_poll;
 pub mod execute_poll;
+pub mod expire_poll;
+pub mod veto_poll;
 pub mod run_execution;
 pub mod reply_execution;
+pub mod reply_execution_result;
+pub mod voting_rewards;
@@ -6,7 +6,7 @@ use terrapoker::mock_querier::{custom_deps, CustomDeps};
 use terrapoker::test_constants::governance::POLL_PROPOSAL_DEPOSIT;
 
 use crate::poll::executions::{reply_execution, REPLY_EXECUTION};
-use crate::poll::states::{Poll, PollExecutionContext};
+use crate::poll::states::{ExecutionResult, Poll, PollExecutionContext};
 use crate::poll::tests::cast_vote::VOTER1;
 use crate::poll::tests::create_poll::{mock_exec_msg, POLL_DESCRIPTION, POLL_LINK, POLL_TITLE, PROPOSER1};
 use crate::tests::init_default;
@@ -98,6 +98,13 @@ fn succeed_failed_reply() {
 
     let poll = Poll::load(&deps.storage, &context.poll_id).unwrap();
     assert_eq!(poll.status, PollStatus::Failed);
+
+    let results = Poll::load_execution_results(&deps.storage, &context.poll_id).unwrap();
+    assert_eq!(results, vec![1, 2, 3].into_iter().map(|order| ExecutionResult {
+        order,
+        success: false,
+        error: Some("Mock err".to_string()),
+    }).collect::<Vec<ExecutionResult>>());
 }
 
 pub fn mock_subcall_response() -> SubMsgResponse {
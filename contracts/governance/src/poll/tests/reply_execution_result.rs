@@ -0,0 +1,116 @@
+use cosmwasm_std::{Addr, Env, Reply, Response, SubMsgResult, Uint128};
+use cosmwasm_std::testing::mock_info;
+
+use terrapoker::common::ContractResult;
+use terrapoker::governance::enumerations::{PollStatus, VoteOption};
+use terrapoker::mock_querier::{custom_deps, CustomDeps};
+use terrapoker::test_constants::governance::*;
+use terrapoker::test_utils::expect_generic_err;
+
+use crate::poll::executions::{reply_execution_result, REPLY_EXECUTION_RESULT};
+use crate::poll::states::{ExecutionResult, Poll, PollExecutionContext};
+use crate::poll::tests::cast_vote::VOTER1;
+use crate::poll::tests::create_poll::{mock_exec_msg, POLL_DESCRIPTION, POLL_TITLE, PROPOSER1};
+use crate::poll::tests::reply_execution::mock_subcall_response;
+use crate::tests::init_default;
+
+pub fn exec(
+    deps: &mut CustomDeps,
+    env: Env,
+    order: u64,
+    result: SubMsgResult,
+) -> ContractResult<Response> {
+    reply_execution_result(deps.as_mut(), env, Reply {
+        id: REPLY_EXECUTION_RESULT + order,
+        result,
+    })
+}
+
+fn execute_default(deps: &mut CustomDeps, atomic: bool) -> Env {
+    init_default(deps.as_mut());
+
+    super::create_poll::exec_with_options(
+        deps,
+        governance_env(),
+        mock_info(GOVERNANCE_TOKEN, &[]),
+        Addr::unchecked(PROPOSER1),
+        POLL_PROPOSAL_DEPOSIT,
+        POLL_TITLE.to_string(),
+        POLL_DESCRIPTION.to_string(),
+        None,
+        vec![mock_exec_msg(1), mock_exec_msg(2)],
+        Some(atomic),
+        None,
+        None,
+    ).unwrap();
+    crate::staking::tests::stake_token_hook::will_success(deps, VOTER1, Uint128::new(100));
+
+    super::cast_vote::will_success(deps, VOTER1, 1, VoteOption::Yes, Uint128::new(100));
+    super::end_poll::will_success(deps, 1);
+
+    let (env, _, _) = super::execute_poll::will_success(deps, 1);
+
+    env
+}
+
+#[test]
+fn succeed_best_effort() {
+    let mut deps = custom_deps();
+
+    let env = execute_default(&mut deps, false);
+
+    exec(&mut deps, env.clone(), 1, SubMsgResult::Ok(mock_subcall_response())).unwrap();
+
+    let poll = Poll::load(&deps.storage, &1).unwrap();
+    assert_eq!(poll.status, PollStatus::Passed);
+
+    exec(&mut deps, env.clone(), 2, SubMsgResult::Err("Mock err".to_string())).unwrap();
+
+    assert!(PollExecutionContext::may_load(&deps.storage).unwrap().is_none());
+
+    let poll = Poll::load(&deps.storage, &1).unwrap();
+    assert_eq!(poll.status, PollStatus::PartiallyExecuted);
+
+    let results = Poll::load_execution_results(&deps.storage, &1).unwrap();
+    assert_eq!(results, vec![
+        ExecutionResult {
+            order: 1,
+            success: true,
+            error: None,
+        },
+        ExecutionResult {
+            order: 2,
+            success: false,
+            error: Some("Mock err".to_string()),
+        },
+    ]);
+}
+
+#[test]
+fn succeed_best_effort_all_failed() {
+    let mut deps = custom_deps();
+
+    let env = execute_default(&mut deps, false);
+
+    exec(&mut deps, env.clone(), 1, SubMsgResult::Err("Mock err".to_string())).unwrap();
+    exec(&mut deps, env.clone(), 2, SubMsgResult::Err("Mock err".to_string())).unwrap();
+
+    let poll = Poll::load(&deps.storage, &1).unwrap();
+    assert_eq!(poll.status, PollStatus::Failed);
+}
+
+#[test]
+fn succeed_atomic() {
+    let mut deps = custom_deps();
+
+    let env = execute_default(&mut deps, true);
+
+    exec(&mut deps, env.clone(), 1, SubMsgResult::Ok(mock_subcall_response())).unwrap();
+
+    // Atomic polls are settled by the reply of RunExecution
+    let poll = Poll::load(&deps.storage, &1).unwrap();
+    assert_eq!(poll.status, PollStatus::Passed);
+
+    let result = exec(&mut deps, env.clone(), 2, SubMsgResult::Err("Mock err".to_string()));
+    expect_generic_err(&result, "Execution 2 failed: Mock err");
+}
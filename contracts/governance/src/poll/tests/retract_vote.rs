@@ -0,0 +1,94 @@
+use cosmwasm_std::{Addr, attr, Env, MessageInfo, Response, Uint128};
+use cosmwasm_std::testing::mock_info;
+
+use terrapoker::common::ContractResult;
+use terrapoker::governance::enumerations::VoteOption;
+use terrapoker::mock_querier::{custom_deps, CustomDeps};
+use terrapoker::test_constants::governance::governance_env;
+use terrapoker::test_utils::expect_generic_err;
+
+use crate::poll::executions::retract_vote;
+use crate::poll::states::Poll;
+use crate::poll::tests::cast_vote::{VOTER1, VOTER2};
+use crate::staking::states::StakerState;
+use crate::tests::init_default;
+
+pub fn exec(
+    deps: &mut CustomDeps,
+    env: Env,
+    info: MessageInfo,
+    poll_id: u64,
+) -> ContractResult<Response> {
+    retract_vote(deps.as_mut(), env, info, poll_id)
+}
+
+pub fn will_success(
+    deps: &mut CustomDeps,
+    voter: &str,
+    poll_id: u64,
+) -> (Env, MessageInfo, Response) {
+    let env = governance_env();
+    let info = mock_info(voter, &[]);
+
+    let response = exec(deps, env.clone(), info.clone(), poll_id).unwrap();
+
+    (env, info, response)
+}
+
+#[test]
+fn succeed() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    super::create_poll::default(&mut deps);
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER1, Uint128::new(100));
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER2, Uint128::new(100));
+
+    let poll_id = 1u64;
+
+    super::cast_vote::will_success(&mut deps, VOTER1, poll_id, VoteOption::Yes, Uint128::new(100));
+    super::cast_vote::will_success(&mut deps, VOTER2, poll_id, VoteOption::Yes, Uint128::new(50));
+
+    let (_, _, response) = will_success(&mut deps, VOTER1, poll_id);
+    assert_eq!(response.attributes, vec![
+        attr("action", "retract_vote"),
+        attr("poll_id", poll_id.to_string()),
+        attr("voter", VOTER1),
+        attr("prev_amount", "100"),
+        attr("prev_voter_option", "yes"),
+    ]);
+
+    let voter_addr = Addr::unchecked(VOTER1);
+
+    let staker_state = StakerState::load_safe(&deps.storage, &voter_addr).unwrap();
+    assert_eq!(staker_state.votes, vec![]);
+
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    assert_eq!(poll.yes_votes, Uint128::new(50));
+    assert!(poll.load_voter(&deps.storage, &voter_addr).is_err());
+}
+
+#[test]
+fn failed_not_voted() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    super::create_poll::default(&mut deps);
+
+    let result = exec(&mut deps, governance_env(), mock_info(VOTER1, &[]), 1);
+
+    expect_generic_err(&result, "User has not voted.");
+}
+
+#[test]
+fn failed_without_poll() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    let result = exec(&mut deps, governance_env(), mock_info(VOTER1, &[]), 1);
+
+    expect_generic_err(&result, "Poll does not exist");
+}
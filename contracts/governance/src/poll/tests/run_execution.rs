@@ -1,14 +1,16 @@
-use cosmwasm_std::{CosmosMsg, Env, MessageInfo, Response, SubMsg, WasmMsg};
+use cosmwasm_std::{BankMsg, Binary, coins, CosmosMsg, Env, MessageInfo, ReplyOn, Response, SubMsg, to_binary, Uint128, WasmMsg};
 use cosmwasm_std::testing::mock_info;
+use cw20::Cw20ExecuteMsg;
 
-use terrapoker::common::{ContractResult, ExecutionMsg};
+use terrapoker::common::{ContractResult, Execution, ExecutionMsg};
 use terrapoker::mock_querier::{custom_deps, CustomDeps};
 use terrapoker::test_constants::default_sender;
-use terrapoker::test_constants::governance::governance_env;
-use terrapoker::test_utils::expect_unauthorized_err;
+use terrapoker::test_constants::governance::*;
+use terrapoker::test_utils::{expect_generic_err, expect_unauthorized_err};
 
-use crate::poll::executions::run_execution;
+use crate::poll::executions::{REPLY_EXECUTION_RESULT, run_execution};
 use crate::poll::tests::create_poll::mock_exec_msg;
+use crate::tests::init_default;
 
 pub fn exec(
     deps: &mut CustomDeps,
@@ -35,17 +37,99 @@ pub fn will_success(
 fn succeed() {
     let mut deps = custom_deps();
 
+    init_default(deps.as_mut());
+
     let executions = vec![
         mock_exec_msg(1),
         mock_exec_msg(2),
     ];
 
     let (_, _, response) = will_success(&mut deps, executions.clone());
-    assert_eq!(response.messages, executions.iter().map(|e| SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: e.contract.to_string(),
-        funds: vec![],
-        msg: e.msg.clone(),
-    }))).collect::<Vec<SubMsg>>())
+    assert_eq!(response.messages, executions.iter().map(|e| SubMsg {
+        id: REPLY_EXECUTION_RESULT + e.order(),
+        msg: Execution::from(&deps.api, e).unwrap().to_cosmos_msg(),
+        gas_limit: None,
+        reply_on: ReplyOn::Always,
+    }).collect::<Vec<SubMsg>>())
+}
+
+#[test]
+fn succeed_non_execute_messages() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    let contract = "terra1fmcjjt6yc9wqup2r06urnrd928jhrde6gcld6n";
+    let recipient = "terra1333veey879eeqcff8j3gfcgwt8cfrg9mq20v6f";
+
+    let executions = vec![
+        ExecutionMsg::Migrate {
+            order: 1,
+            contract: contract.to_string(),
+            new_code_id: 10,
+            msg: Binary::default(),
+        },
+        ExecutionMsg::UpdateAdmin {
+            order: 2,
+            contract: contract.to_string(),
+            admin: recipient.to_string(),
+        },
+        ExecutionMsg::ClearAdmin {
+            order: 3,
+            contract: contract.to_string(),
+        },
+        ExecutionMsg::BankSend {
+            order: 4,
+            to_address: recipient.to_string(),
+            amount: coins(100, "uusd"),
+        },
+    ];
+
+    let (_, _, response) = will_success(&mut deps, executions);
+    assert_eq!(response.messages.iter().map(|m| m.msg.clone()).collect::<Vec<CosmosMsg>>(), vec![
+        CosmosMsg::Wasm(WasmMsg::Migrate {
+            contract_addr: contract.to_string(),
+            new_code_id: 10,
+            msg: Binary::default(),
+        }),
+        CosmosMsg::Wasm(WasmMsg::UpdateAdmin {
+            contract_addr: contract.to_string(),
+            admin: recipient.to_string(),
+        }),
+        CosmosMsg::Wasm(WasmMsg::ClearAdmin {
+            contract_addr: contract.to_string(),
+        }),
+        CosmosMsg::Bank(BankMsg::Send {
+            to_address: recipient.to_string(),
+            amount: coins(100, "uusd"),
+        }),
+    ]);
+}
+
+#[test]
+fn failed_denied_by_policy() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    let executions = vec![
+        ExecutionMsg::Execute {
+            order: 1,
+            contract: GOVERNANCE_TOKEN.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                owner: GOVERNANCE.to_string(),
+                recipient: GOVERNANCE.to_string(),
+                amount: Uint128::new(1),
+            }).unwrap(),
+            funds: vec![],
+        },
+    ];
+
+    let env = governance_env();
+    let info = mock_info(env.contract.address.as_str(), &[]);
+
+    let result = exec(&mut deps, env, info, executions);
+    expect_generic_err(&result, &format!("Execution message not allowed: transfer_from on {}", GOVERNANCE_TOKEN));
 }
 
 #[test]
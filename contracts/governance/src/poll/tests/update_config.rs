@@ -9,7 +9,7 @@ use terrapoker::test_utils::{expect_generic_err, expect_unauthorized_err};
 
 use crate::poll::executions::update_poll_config;
 use crate::poll::states::PollConfig;
-use crate::tests::init_default;
+use crate::tests::{init_default, POLL_EXPEDITED_PROPOSAL_DEPOSIT, POLL_EXPEDITED_THRESHOLD_PERCENT, POLL_EXPEDITED_VOTING_PERIOD};
 
 pub fn exec(
     deps: &mut CustomDeps,
@@ -30,6 +30,21 @@ pub fn exec(
         voting_period,
         execution_delay_period,
         proposal_deposit,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
+        None,
     )
 }
 
@@ -152,6 +167,52 @@ fn failed_invalid_execution_delay_period() {
     expect_generic_err(&result, "execution_delay_period must be greater than 1000");
 }
 
+#[test]
+fn failed_invalid_expedited() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    let result = exec(
+        &mut deps,
+        governance_env(),
+        mock_info(GOVERNANCE, &[]),
+        None,
+        None,
+        Some(POLL_EXPEDITED_VOTING_PERIOD),
+        None,
+        None,
+    );
+
+    expect_generic_err(&result, "expedited_voting_period must be greater than 0 and less than voting_period");
+
+    let result = exec(
+        &mut deps,
+        governance_env(),
+        mock_info(GOVERNANCE, &[]),
+        None,
+        Some(Decimal::percent(POLL_EXPEDITED_THRESHOLD_PERCENT + 1)),
+        None,
+        None,
+        None,
+    );
+
+    expect_generic_err(&result, "expedited_threshold must not be less than threshold");
+
+    let result = exec(
+        &mut deps,
+        governance_env(),
+        mock_info(GOVERNANCE, &[]),
+        None,
+        None,
+        None,
+        None,
+        Some(POLL_EXPEDITED_PROPOSAL_DEPOSIT + Uint128::new(1)),
+    );
+
+    expect_generic_err(&result, "expedited_proposal_deposit must not be less than proposal_deposit");
+}
+
 #[test]
 fn failed_invalid_permission() {
     let mut deps = custom_deps();
@@ -167,7 +228,6 @@ fn failed_invalid_permission() {
         None,
         None,
         None,
-        None,
     );
 
     expect_unauthorized_err(&result);
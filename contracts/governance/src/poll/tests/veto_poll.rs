@@ -0,0 +1,160 @@
+use cosmwasm_std::{attr, Addr, Env, MessageInfo, Response, Uint128};
+use cosmwasm_std::testing::mock_info;
+
+use terrapoker::common::ContractResult;
+use terrapoker::governance::enumerations::{PollStatus, VoteOption};
+use terrapoker::governance::models::GuardianMsg;
+use terrapoker::mock_querier::{custom_deps, CustomDeps};
+use terrapoker::test_constants::governance::*;
+use terrapoker::test_utils::{expect_generic_err, expect_unauthorized_err};
+
+use crate::poll::executions::{update_guardian, veto_poll};
+use crate::poll::states::{Guardian, Poll, PollConfig};
+use crate::poll::tests::cast_vote::VOTER1;
+use crate::poll::tests::create_poll::{mock_exec_msg, POLL_DESCRIPTION, POLL_LINK, POLL_TITLE, PROPOSER1};
+use crate::tests::init_default;
+
+pub const GUARDIAN: &str = "terra1guardian0000000000000000000000000000000";
+pub const GUARDIAN_EXPIRY_HEIGHT: u64 = 100_000_000;
+
+pub fn exec(deps: &mut CustomDeps, env: Env, info: MessageInfo, poll_id: u64) -> ContractResult<Response> {
+    veto_poll(deps.as_mut(), env, info, poll_id)
+}
+
+pub fn exec_update_guardian(deps: &mut CustomDeps, env: Env, info: MessageInfo, guardian: Option<GuardianMsg>) -> ContractResult<Response> {
+    update_guardian(deps.as_mut(), env, info, guardian)
+}
+
+fn pass_with_guardian(deps: &mut CustomDeps) -> u64 {
+    init_default(deps.as_mut());
+
+    exec_update_guardian(deps, governance_env(), governance_sender(), Some(GuardianMsg {
+        address: GUARDIAN.to_string(),
+        expiry_height: GUARDIAN_EXPIRY_HEIGHT,
+    })).unwrap();
+
+    super::create_poll::will_success(
+        deps,
+        PROPOSER1,
+        POLL_PROPOSAL_DEPOSIT,
+        POLL_TITLE,
+        POLL_DESCRIPTION,
+        Some(POLL_LINK),
+        vec![mock_exec_msg(1)],
+    );
+    crate::staking::tests::stake_token_hook::will_success(deps, VOTER1, Uint128::new(100));
+
+    let poll_id = 1u64;
+
+    super::cast_vote::will_success(deps, VOTER1, poll_id, VoteOption::Yes, Uint128::new(100));
+    super::end_poll::will_success(deps, poll_id);
+
+    poll_id
+}
+
+#[test]
+fn succeed() {
+    let mut deps = custom_deps();
+
+    let poll_id = pass_with_guardian(&mut deps);
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+
+    let response = exec(
+        &mut deps,
+        governance_env_height(poll.end_height + 1),
+        mock_info(GUARDIAN, &[]),
+        poll_id,
+    ).unwrap();
+    assert_eq!(response.attributes, vec![
+        attr("action", "veto_poll"),
+        attr("poll_id", poll_id.to_string()),
+        attr("guardian", GUARDIAN),
+    ]);
+
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    assert_eq!(poll.status, PollStatus::Vetoed);
+}
+
+#[test]
+fn failed_invalid_permission() {
+    let mut deps = custom_deps();
+
+    let poll_id = pass_with_guardian(&mut deps);
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+
+    let result = exec(
+        &mut deps,
+        governance_env_height(poll.end_height + 1),
+        mock_info(VOTER1, &[]),
+        poll_id,
+    );
+    expect_unauthorized_err(&result);
+}
+
+#[test]
+fn failed_after_execution_delay() {
+    let mut deps = custom_deps();
+
+    let poll_id = pass_with_guardian(&mut deps);
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+
+    let result = exec(
+        &mut deps,
+        governance_env_height(poll.end_height + POLL_EXECUTION_DELAY_PERIOD),
+        mock_info(GUARDIAN, &[]),
+        poll_id,
+    );
+    expect_generic_err(&result, "Execution delay period has expired");
+}
+
+#[test]
+fn failed_guardian_expired() {
+    let mut deps = custom_deps();
+
+    let poll_id = pass_with_guardian(&mut deps);
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+
+    let mut poll_config = PollConfig::load(&deps.storage).unwrap();
+    poll_config.guardian = Some(Guardian {
+        address: Addr::unchecked(GUARDIAN),
+        expiry_height: poll.end_height + 1,
+    });
+    poll_config.save(&mut deps.storage).unwrap();
+
+    let result = exec(
+        &mut deps,
+        governance_env_height(poll.end_height + 1),
+        mock_info(GUARDIAN, &[]),
+        poll_id,
+    );
+    expect_generic_err(&result, "Guardian power has expired");
+}
+
+#[test]
+fn succeed_remove_guardian() {
+    let mut deps = custom_deps();
+
+    let poll_id = pass_with_guardian(&mut deps);
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+
+    exec_update_guardian(&mut deps, governance_env(), governance_sender(), None).unwrap();
+    assert_eq!(PollConfig::load(&deps.storage).unwrap().guardian, None);
+
+    let result = exec(
+        &mut deps,
+        governance_env_height(poll.end_height + 1),
+        mock_info(GUARDIAN, &[]),
+        poll_id,
+    );
+    expect_unauthorized_err(&result);
+}
+
+#[test]
+fn failed_update_guardian_invalid_permission() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    let result = exec_update_guardian(&mut deps, governance_env(), mock_info(GUARDIAN, &[]), None);
+    expect_unauthorized_err(&result);
+}
@@ -0,0 +1,191 @@
+use cosmwasm_std::{Addr, Decimal, Env, MessageInfo, Response, SubMsg, Uint128};
+use cosmwasm_std::testing::mock_info;
+
+use terrapoker::common::ContractResult;
+use terrapoker::governance::enumerations::VoteOption;
+use terrapoker::message_factories;
+use terrapoker::mock_querier::{custom_deps, CustomDeps};
+use terrapoker::test_constants::governance::*;
+use terrapoker::test_utils::{expect_generic_err, expect_unauthorized_err};
+
+use crate::poll::executions::{allocate_voting_reward, claim_voting_rewards, fund_voting_reward};
+use crate::poll::states::Poll;
+use crate::poll::tests::cast_vote::{VOTER1, VOTER2};
+use crate::staking::queries::get_exchange_rate;
+use crate::staking::states::{StakerState, StakingState};
+use crate::tests::init_default;
+
+pub fn exec_fund(deps: &mut CustomDeps, env: Env, info: MessageInfo, poll_id: u64, amount: Uint128) -> ContractResult<Response> {
+    let sender = Addr::unchecked(info.sender.as_str());
+
+    deps.querier.plus_token_balances(&[(
+        STAKING_TOKEN,
+        &[(GOVERNANCE, &amount)],
+    )]);
+
+    fund_voting_reward(deps.as_mut(), env, info, sender, amount, Some(poll_id))
+}
+
+pub fn will_success_fund(deps: &mut CustomDeps, poll_id: u64, amount: Uint128) -> (Env, MessageInfo, Response) {
+    let env = governance_env();
+    let info = mock_info(STAKING_TOKEN, &[]);
+
+    let response = exec_fund(deps, env.clone(), info.clone(), poll_id, amount).unwrap();
+
+    (env, info, response)
+}
+
+pub fn exec_allocate(deps: &mut CustomDeps, env: Env, info: MessageInfo, poll_id: u64, amount: Uint128) -> ContractResult<Response> {
+    allocate_voting_reward(deps.as_mut(), env, info, poll_id, amount)
+}
+
+pub fn exec_claim(deps: &mut CustomDeps, env: Env, info: MessageInfo, poll_ids: Vec<u64>) -> ContractResult<Response> {
+    claim_voting_rewards(deps.as_mut(), env, info, poll_ids)
+}
+
+#[test]
+fn succeed() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    super::create_poll::default(&mut deps);
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER1, Uint128::new(100));
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER2, Uint128::new(100));
+
+    let poll_id = 1u64;
+
+    will_success_fund(&mut deps, poll_id, Uint128::new(300));
+
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    assert_eq!(poll.voting_reward, Uint128::new(300));
+    assert_eq!(StakingState::load(&deps.storage).unwrap().total_voting_reward, Uint128::new(300));
+
+    super::cast_vote::will_success(&mut deps, VOTER1, poll_id, VoteOption::Yes, Uint128::new(100));
+    super::cast_vote::will_success(&mut deps, VOTER2, poll_id, VoteOption::No, Uint128::new(50));
+
+    let result = exec_claim(&mut deps, governance_env(), mock_info(VOTER1, &[]), vec![poll_id]);
+    expect_generic_err(&result, "No voting rewards to claim");
+
+    let (env, _, _) = super::end_poll::will_success(&mut deps, poll_id);
+
+    let response = exec_claim(&mut deps, env.clone(), mock_info(VOTER1, &[]), vec![poll_id]).unwrap();
+    assert_eq!(response.messages, vec![
+        SubMsg::new(message_factories::cw20_transfer(
+            &Addr::unchecked(STAKING_TOKEN),
+            &Addr::unchecked(VOTER1),
+            Uint128::new(200),
+        )),
+    ]);
+
+    let mut staker_state = StakerState::load_safe(&deps.storage, &Addr::unchecked(VOTER2)).unwrap();
+    staker_state.clean_votes(&deps.storage);
+    assert_eq!(staker_state.voting_rewards, vec![(poll_id, Uint128::new(100))]);
+
+    assert_eq!(StakingState::load(&deps.storage).unwrap().total_voting_reward, Uint128::new(100));
+
+    let result = exec_claim(&mut deps, env, mock_info(VOTER1, &[]), vec![poll_id]);
+    expect_generic_err(&result, "No voting rewards to claim");
+}
+
+#[test]
+fn succeed_release_without_votes() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    super::create_poll::default(&mut deps);
+
+    let poll_id = 1u64;
+
+    will_success_fund(&mut deps, poll_id, Uint128::new(300));
+
+    super::end_poll::will_success(&mut deps, poll_id);
+
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    assert_eq!(poll.voting_reward, Uint128::zero());
+    assert_eq!(StakingState::load(&deps.storage).unwrap().total_voting_reward, Uint128::zero());
+}
+
+#[test]
+fn succeed_sweep_dust() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    super::create_poll::default(&mut deps);
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER1, Uint128::new(100));
+    crate::staking::tests::stake_token_hook::will_success(&mut deps, VOTER2, Uint128::new(100));
+
+    let poll_id = 1u64;
+
+    will_success_fund(&mut deps, poll_id, Uint128::new(100));
+
+    super::cast_vote::will_success(&mut deps, VOTER1, poll_id, VoteOption::Yes, Uint128::new(100));
+    super::cast_vote::will_success(&mut deps, VOTER2, poll_id, VoteOption::No, Uint128::new(50));
+
+    let (env, _, _) = super::end_poll::will_success(&mut deps, poll_id);
+
+    // 66 and 33 are claimable, the rounding dust no voter can claim is swept
+    exec_claim(&mut deps, env.clone(), mock_info(VOTER1, &[]), vec![poll_id]).unwrap();
+    assert_eq!(StakingState::load(&deps.storage).unwrap().total_voting_reward, Uint128::new(33));
+
+    let response = get_exchange_rate(deps.as_ref(), env.clone(), Some(env.block.height)).unwrap();
+    assert_eq!(response.exchange_rate, Decimal::from_ratio(201u128, 200u128));
+
+    exec_claim(&mut deps, env, mock_info(VOTER2, &[]), vec![poll_id]).unwrap();
+    assert_eq!(StakingState::load(&deps.storage).unwrap().total_voting_reward, Uint128::zero());
+
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    assert_eq!(poll.settled_voting_reward, Uint128::new(100));
+}
+
+#[test]
+fn succeed_allocate_unallocated() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    super::create_poll::default(&mut deps);
+
+    let poll_id = 1u64;
+
+    deps.querier.plus_token_balances(&[(
+        STAKING_TOKEN,
+        &[(GOVERNANCE, &Uint128::new(300))],
+    )]);
+
+    let sender = Addr::unchecked(VOTER1);
+    fund_voting_reward(deps.as_mut(), governance_env(), mock_info(STAKING_TOKEN, &[]), sender, Uint128::new(300), None).unwrap();
+
+    let staking_state = StakingState::load(&deps.storage).unwrap();
+    assert_eq!(staking_state.unallocated_voting_reward, Uint128::new(300));
+    assert_eq!(staking_state.total_voting_reward, Uint128::zero());
+
+    exec_allocate(&mut deps, governance_env(), governance_sender(), poll_id, Uint128::new(200)).unwrap();
+
+    let poll = Poll::load(&deps.storage, &poll_id).unwrap();
+    assert_eq!(poll.voting_reward, Uint128::new(200));
+
+    let staking_state = StakingState::load(&deps.storage).unwrap();
+    assert_eq!(staking_state.unallocated_voting_reward, Uint128::new(100));
+    assert_eq!(staking_state.total_voting_reward, Uint128::new(200));
+
+    let result = exec_allocate(&mut deps, governance_env(), governance_sender(), poll_id, Uint128::new(200));
+    expect_generic_err(&result, "Insufficient unallocated voting reward");
+
+    let result = exec_allocate(&mut deps, governance_env(), mock_info(VOTER1, &[]), poll_id, Uint128::new(100));
+    expect_unauthorized_err(&result);
+}
+
+#[test]
+fn failed_invalid_token() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    super::create_poll::default(&mut deps);
+
+    let result = exec_fund(&mut deps, governance_env(), mock_info(GOVERNANCE_TOKEN, &[]), 1, Uint128::new(300));
+    expect_unauthorized_err(&result);
+}
@@ -1,13 +1,13 @@
-use cosmwasm_std::{Addr, DepsMut, Env, MessageInfo, Response, StdError, Uint128, SubMsg, CosmosMsg, WasmMsg, to_binary, Decimal, Deps};
+use cosmwasm_std::{Addr, Binary, DepsMut, Env, MessageInfo, Response, StdError, Uint128, SubMsg, CosmosMsg, WasmMsg, to_binary, Decimal, Deps};
 use cw20::Cw20ExecuteMsg;
 
 use terrapoker::common::ContractResult;
 use terrapoker::errors::ContractError;
 
-use crate::common::states::{ContractConfig, load_contract_staking_token_balance};
+use crate::common::states::{ContractConfig, load_contract_staking_token_balance, load_gov_token_total_supply};
 
-use super::states::{StakerState, StakingState};
-use terrapoker::utils::{make_response};
+use super::states::{checkpoint_exchange_rate, save_exchange_rate, StakerState, StakingState};
+use terrapoker::utils::{addr_opt_validate, make_response, validate_zero_to_one};
 use terrapoker::message_factories;
 use terrapoker::governance::execute_msgs::{StakingConfigInitMsg, ExecuteMsg};
 use crate::staking::queries::{simulate_unstake_amount, simulate_stake_amount};
@@ -19,33 +19,49 @@ pub fn instantiate(
     _info: MessageInfo,
     msg: StakingConfigInitMsg,
 ) -> ContractResult<Response> {
+    // Validate
+    validate_zero_to_one(msg.instant_unstake_fee, "instant_unstake_fee")?;
+
     // Execute
     let response = make_response("instantiate");
 
     StakingConfig {
         distributor: msg.distributor.map(|d| deps.api.addr_validate(d.as_str())).transpose()?,
         unstake_lock_period: msg.unstake_lock_period,
+        instant_unstake_fee: msg.instant_unstake_fee,
+        instant_unstake_fee_recipient: addr_opt_validate(deps.api, &msg.instant_unstake_fee_recipient)?,
     }.save(deps.storage)?;
 
     StakingState {
-        total_unstake_locked: Uint128::zero()
+        total_unstake_locked: Uint128::zero(),
+        total_voting_reward: Uint128::zero(),
+        unallocated_voting_reward: Uint128::zero(),
     }.save(deps.storage)?;
 
     Ok(response)
 }
 
+#[allow(clippy::too_many_arguments)]
 pub fn update_staking_config(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
     distributor: Option<String>,
     unstake_lock_period: Option<u64>,
+    instant_unstake_fee: Option<Decimal>,
+    instant_unstake_fee_recipient: Option<String>,
+    clear_instant_unstake_fee_recipient: Option<bool>,
 ) -> ContractResult<Response> {
     // Validate
     if env.contract.address != info.sender {
         return Err(ContractError::Unauthorized {});
     }
 
+    let clear_instant_unstake_fee_recipient = clear_instant_unstake_fee_recipient.unwrap_or(false);
+    if clear_instant_unstake_fee_recipient && instant_unstake_fee_recipient.is_some() {
+        return Err(ContractError::Std(StdError::generic_err("Can not set and clear instant_unstake_fee_recipient at once")));
+    }
+
     // Execute
     let mut response = make_response("update_staking_config");
 
@@ -61,6 +77,22 @@ pub fn update_staking_config(
         response = response.add_attribute("is_updated_unstake_lock_period", "true");
     }
 
+    if let Some(instant_unstake_fee) = instant_unstake_fee {
+        validate_zero_to_one(instant_unstake_fee, "instant_unstake_fee")?;
+        config.instant_unstake_fee = instant_unstake_fee;
+        response = response.add_attribute("is_updated_instant_unstake_fee", "true");
+    }
+
+    if let Some(instant_unstake_fee_recipient) = instant_unstake_fee_recipient {
+        config.instant_unstake_fee_recipient = Some(deps.api.addr_validate(instant_unstake_fee_recipient.as_str())?);
+        response = response.add_attribute("is_updated_instant_unstake_fee_recipient", "true");
+    }
+
+    if clear_instant_unstake_fee_recipient {
+        config.instant_unstake_fee_recipient = None;
+        response = response.add_attribute("is_updated_instant_unstake_fee_recipient", "true");
+    }
+
     config.save(deps.storage)?;
 
     Ok(response)
@@ -106,6 +138,36 @@ pub fn stake_token(
     Ok(response)
 }
 
+// Staking rewards sent by the distributor with the StakingReward message raise the exchange rate.
+// Rewards transferred without the message are only reflected by the next checkpoint.
+pub fn receive_staking_reward(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    sender: Addr,
+    amount: Uint128,
+) -> ContractResult<Response> {
+    // Validate
+    let contract_config = ContractConfig::load(deps.storage)?;
+    if !contract_config.is_staking_token(&info.sender) {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if amount.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err("Insufficient funds sent")));
+    }
+
+    // Execute
+    let mut response = make_response("receive_staking_reward");
+
+    checkpoint_exchange_rate(deps, env.block.height, Uint128::zero(), Uint128::zero())?;
+
+    response = response.add_attribute("sender", sender.as_str());
+    response = response.add_attribute("amount", amount.to_string());
+
+    Ok(response)
+}
+
 pub fn stake_token_hook(
     deps: DepsMut,
     env: Env,
@@ -133,6 +195,10 @@ pub fn stake_token_hook(
         .checked_sub(amount)?;
     let mint_amount = simulate_stake_amount(deps.as_ref(), staked_amount, amount)?;
 
+    // the distributor has been triggered already, so the checkpoint includes its rewards.
+    let total_supply = load_gov_token_total_supply(deps.as_ref(), None)?;
+    save_exchange_rate(deps.storage, env.block.height, staked_amount, total_supply)?;
+
     response = response.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
         contract_addr: contract_config.governance_token.to_string(),
         funds: vec![],
@@ -155,6 +221,7 @@ pub fn unstake_token(
     info: MessageInfo,
     staker: Addr,
     amount: Uint128,
+    instant: bool,
 ) -> ContractResult<Response> {
     //xtpt -> tpt
     let contract_config = ContractConfig::load(deps.storage)?;
@@ -175,12 +242,21 @@ pub fn unstake_token(
         )));
     }
 
-    response.messages.push(SubMsg::new(message_factories::wasm_execute(
-        &env.contract.address,
-        &ExecuteMsg::UnstakeGovernanceTokenHook {
+    let hook_msg = if instant {
+        ExecuteMsg::InstantUnstakeGovernanceTokenHook {
             staker: staker.to_string(),
             amount,
-        },
+        }
+    } else {
+        ExecuteMsg::UnstakeGovernanceTokenHook {
+            staker: staker.to_string(),
+            amount,
+        }
+    };
+
+    response.messages.push(SubMsg::new(message_factories::wasm_execute(
+        &env.contract.address,
+        &hook_msg,
     )));
 
     Ok(response)
@@ -188,7 +264,7 @@ pub fn unstake_token(
 
 // Withdraw amount if not staked. By default all funds will be withdrawn.
 pub fn unstake_token_hook(
-    deps: DepsMut,
+    mut deps: DepsMut,
     env: Env,
     info: MessageInfo,
     staker: String,
@@ -212,8 +288,10 @@ pub fn unstake_token_hook(
 
     let withdraw_amount = simulate_unstake_amount(deps.as_ref(), amount)?;
 
+    checkpoint_exchange_rate(deps.branch(), env.block.height, Uint128::zero(), Uint128::zero())?;
+
     let config = StakingConfig::load(deps.storage)?;
-    staker_state.unstake_locked_list.push((env.block.height + config.unstake_lock_period, withdraw_amount));
+    staker_state.add_unstake_locked(deps.storage, env.block.height + config.unstake_lock_period, withdraw_amount)?;
     staker_state.save(deps.storage)?;
 
     staking_state.total_unstake_locked += withdraw_amount;
@@ -233,33 +311,165 @@ pub fn unstake_token_hook(
     Ok(response)
 }
 
+// Pays out the unstaked amount immediately, except the instant unstake fee.
+pub fn instant_unstake_token_hook(
+    mut deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    staker: String,
+    amount: Uint128, //xtpt
+) -> ContractResult<Response> {
+    // Validate
+    if env.contract.address != info.sender {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let sender = deps.api.addr_validate(staker.as_str())?;
+
+    // Execute
+    let mut response = make_response("instant_unstake_token_hook");
+
+    let withdraw_amount = simulate_unstake_amount(deps.as_ref(), amount)?;
+
+    let config = StakingConfig::load(deps.storage)?;
+    let fee_amount = withdraw_amount * config.instant_unstake_fee;
+    let payout_amount = withdraw_amount.checked_sub(fee_amount)?;
+
+    // a fee staying in the pool raises the exchange rate, so the checkpoint is taken after the payout.
+    let released_amount = if config.instant_unstake_fee_recipient.is_some() {
+        withdraw_amount
+    } else {
+        payout_amount
+    };
+    checkpoint_exchange_rate(deps.branch(), env.block.height, released_amount, amount)?;
+
+    let contract_config = ContractConfig::load(deps.storage)?;
+    response = response.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: contract_config.governance_token.to_string(),
+        funds: vec![],
+        msg: to_binary(&Cw20ExecuteMsg::Burn {
+            amount,
+        })?,
+    }));
+
+    if !payout_amount.is_zero() {
+        response = response.add_message(message_factories::cw20_transfer(
+            &contract_config.staking_token,
+            &sender,
+            payout_amount,
+        ));
+    }
+
+    // the fee stays in the pool and raises the exchange rate if no recipient is set
+    if let Some(recipient) = config.instant_unstake_fee_recipient {
+        if !fee_amount.is_zero() {
+            response = response.add_message(message_factories::cw20_transfer(
+                &contract_config.staking_token,
+                &recipient,
+                fee_amount,
+            ));
+        }
+    }
+
+    response = response.add_attribute("staker", sender.as_str());
+    response = response.add_attribute("unstake_amount", withdraw_amount);
+    response = response.add_attribute("fee_amount", fee_amount);
+    response = response.add_attribute("payout_amount", payout_amount);
+
+    Ok(response)
+}
+
+// Stakes the locked amount again through the stake_token_hook
+pub fn cancel_unstake(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    height: u64,
+) -> ContractResult<Response> {
+    // Validate
+    let staker_state = StakerState::load_safe(deps.storage, &info.sender)?;
+
+    let amount = staker_state.remove_unstake_locked(deps.storage, height)?;
+    if amount.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err("Unstake request does not exist")));
+    }
+
+    // Execute
+    let mut response = make_response("cancel_unstake");
+
+    let mut staking_state = StakingState::load(deps.storage)?;
+    staking_state.total_unstake_locked = staking_state.total_unstake_locked.checked_sub(amount)?;
+    staking_state.save(deps.storage)?;
+
+    let config = StakingConfig::load(deps.storage)?;
+
+    if let Some(distributor) = config.distributor {
+        response.messages.push(SubMsg::new(message_factories::wasm_execute(
+            &distributor,
+            &terrapoker::distributor::execute_msgs::ExecuteMsg::Distribute {
+                id: None,
+            },
+        )));
+    }
+
+    response.messages.push(SubMsg::new(message_factories::wasm_execute(
+        &env.contract.address,
+        &ExecuteMsg::StakeGovernanceTokenHook {
+            staker: info.sender.to_string(),
+            amount,
+        },
+    )));
+
+    response = response.add_attribute("staker", info.sender.as_str());
+    response = response.add_attribute("height", height.to_string());
+    response = response.add_attribute("amount", amount.to_string());
+
+    Ok(response)
+}
+
 pub fn claim_unstaked_token(
     deps: DepsMut,
     env: Env,
     info: MessageInfo,
+    limit: Option<u32>,
+    recipient: Option<String>,
+    message: Option<Binary>,
 ) -> ContractResult<Response> {
+    let recipient = addr_opt_validate(deps.api, &recipient)?.unwrap_or(info.sender.clone());
 
-    let mut staker_state = StakerState::load_safe(deps.storage, &info.sender)?;
-    let claimable_amount = staker_state.get_unstake_claimable_amount(env.block.height);
+    let staker_state = StakerState::load_safe(deps.storage, &info.sender)?;
+    let claimable_amount = staker_state.take_unstake_claimable(deps.storage, env.block.height, limit)?;
+    if claimable_amount.is_zero() {
+        return Err(ContractError::Std(StdError::generic_err("Nothing to claim")));
+    }
 
     let mut staking_state = StakingState::load(deps.storage)?;
     staking_state.total_unstake_locked -= claimable_amount;
     staking_state.save(deps.storage)?;
 
-    staker_state.clean_unstake_locked_list(env.block.height);
-    staker_state.save(deps.storage)?;
-
     let contract_config = ContractConfig::load(deps.storage)?;
     let mut response = make_response("claim_unstaked_token");
-    response = response.add_message(CosmosMsg::Wasm(WasmMsg::Execute {
-        contract_addr: contract_config.staking_token.to_string(),
-        funds: vec![],
-        msg: to_binary(&Cw20ExecuteMsg::Transfer {
-            recipient: info.sender.to_string(),
-            amount: claimable_amount,
-        })
-            .unwrap(),
-    }));
+
+    let claim_msg = if let Some(message) = message {
+        message_factories::wasm_execute(
+            &contract_config.staking_token,
+            &Cw20ExecuteMsg::Send {
+                contract: recipient.to_string(),
+                amount: claimable_amount,
+                msg: message,
+            },
+        )
+    } else {
+        message_factories::cw20_transfer(
+            &contract_config.staking_token,
+            &recipient,
+            claimable_amount,
+        )
+    };
+    response = response.add_message(claim_msg);
+
+    response = response.add_attribute("recipient", recipient.as_str());
+    response = response.add_attribute("amount", claimable_amount.to_string());
 
     Ok(response)
 }
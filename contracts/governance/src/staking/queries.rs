@@ -1,13 +1,14 @@
 use std::cmp::min;
-use cosmwasm_std::{Decimal, Deps, Env, Uint128};
+use cosmwasm_std::{Decimal, Deps, Env, StdError, Uint128};
 
 use terrapoker::common::ContractResult;
-use terrapoker::governance::models::VoteInfoMsg;
-use terrapoker::governance::query_msgs::{AllStakersResponse, StakerInfoResponse, StakerStateResponse, StakingStateResponse, VotingPowerResponse};
+use terrapoker::governance::query_msgs::{AllStakersResponse, ExchangeRateHistoryResponse, ExchangeRateResponse, StakerInfoResponse, StakerStateResponse, SimulateUnstakeAmountResponse, StakerVotesResponse, StakingAprResponse, StakingStateResponse, UnstakeRequestsResponse, VotingPowerResponse};
+use terrapoker::errors::ContractError;
 
 use crate::common::states::{load_contract_staking_token_balance, load_gov_token_balance, load_gov_token_total_supply};
 
-use super::states::{StakerState, StakingState};
+use super::states::{load_exchange_rate_at, load_exchange_rates, StakerState, StakingState};
+use crate::poll::states::Poll;
 use crate::staking::states::StakingConfig;
 
 
@@ -19,6 +20,8 @@ pub fn get_staking_state(deps: Deps, _env: Env) -> ContractResult<StakingStateRe
     let staking_state = StakingState::load(deps.storage)?;
     Ok(StakingStateResponse {
         total_unstake_locked: staking_state.total_unstake_locked,
+        total_voting_reward: staking_state.total_voting_reward,
+        unallocated_voting_reward: staking_state.unallocated_voting_reward,
     })
 }
 
@@ -40,21 +43,45 @@ pub fn get_staker_state(
     let votes = staker_state
         .votes
         .iter()
-        .map(|(poll_id, vote)| {
-            let msg = VoteInfoMsg {
-                voter: vote.voter.to_string(),
-                option: vote.option.clone(),
-                amount: vote.amount,
-            };
-
-            (*poll_id, msg)
-        })
+        .map(|(poll_id, vote)| (*poll_id, vote.to_msg()))
         .collect();
 
     Ok(StakerStateResponse {
         votes,
         locked_balance: staker_state.get_vote_locked_balance(),
-        unstake_locked_list: staker_state.unstake_locked_list,
+        voting_rewards: staker_state.voting_rewards,
+    })
+}
+
+pub fn query_staker_votes(
+    deps: Deps,
+    _env: Env,
+    address: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> ContractResult<StakerVotesResponse> {
+    let address = deps.api.addr_validate(&address)?;
+
+    let votes = Poll::read_staker_votes(deps.storage, &address, start_after, limit)?.iter()
+        .map(|(poll_id, vote)| (*poll_id, vote.to_msg()))
+        .collect();
+
+    Ok(StakerVotesResponse {
+        votes,
+    })
+}
+
+pub fn get_unstake_requests(
+    deps: Deps,
+    _env: Env,
+    address: String,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> ContractResult<UnstakeRequestsResponse> {
+    let address = deps.api.addr_validate(&address)?;
+
+    Ok(UnstakeRequestsResponse {
+        requests: StakerState::load_unstake_locked(deps.storage, &address, start_after, limit)?,
     })
 }
 
@@ -119,8 +146,17 @@ pub fn get_simulate_unstake_amount(
     deps: Deps,
     _env: Env,
     amount: Uint128,
-) -> ContractResult<Uint128> {
-    simulate_unstake_amount(deps, amount)
+) -> ContractResult<SimulateUnstakeAmountResponse> {
+    let delayed_amount = simulate_unstake_amount(deps, amount)?;
+
+    let config = StakingConfig::load(deps.storage)?;
+    let instant_fee = delayed_amount * config.instant_unstake_fee;
+
+    Ok(SimulateUnstakeAmountResponse {
+        delayed_amount,
+        instant_amount: delayed_amount.checked_sub(instant_fee)?,
+        instant_fee,
+    })
 }
 
 pub fn simulate_unstake_amount(
@@ -137,4 +173,87 @@ pub fn simulate_unstake_amount(
     let amount = staked_amount * share;
 
     Ok(amount)
-}
\ No newline at end of file
+}
+
+// 6 seconds per block
+const DEFAULT_BLOCKS_PER_YEAR: u64 = 5_256_000;
+
+pub fn load_exchange_rate(deps: Deps) -> ContractResult<Decimal> {
+    // xTPT => TPT
+
+    let xtpt_total_supply = load_gov_token_total_supply(deps, None)?;
+    if xtpt_total_supply.is_zero() {
+        return Ok(Decimal::one());
+    }
+
+    let staked_amount = load_contract_staking_token_balance(deps)?;
+
+    Ok(Decimal::from_ratio(staked_amount, xtpt_total_supply))
+}
+
+pub fn get_exchange_rate(
+    deps: Deps,
+    env: Env,
+    height: Option<u64>,
+) -> ContractResult<ExchangeRateResponse> {
+    let exchange_rate = match height {
+        None => load_exchange_rate(deps)?,
+        Some(height) => load_exchange_rate_at(deps.storage, height)?
+            .map(|(_, exchange_rate)| exchange_rate)
+            .ok_or_else(|| StdError::generic_err("No exchange rate recorded at the height"))?,
+    };
+
+    Ok(ExchangeRateResponse {
+        height: height.unwrap_or(env.block.height),
+        exchange_rate,
+    })
+}
+
+pub fn get_exchange_rate_history(
+    deps: Deps,
+    _env: Env,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> ContractResult<ExchangeRateHistoryResponse> {
+    Ok(ExchangeRateHistoryResponse {
+        exchange_rates: load_exchange_rates(deps.storage, start_after, limit)?.iter()
+            .map(|(height, exchange_rate)| ExchangeRateResponse {
+                height: *height,
+                exchange_rate: *exchange_rate,
+            })
+            .collect(),
+    })
+}
+
+// annualises the exchange rate growth since the latest checkpoint at or before the window start.
+pub fn get_staking_apr(
+    deps: Deps,
+    env: Env,
+    window: u64,
+    blocks_per_year: Option<u64>,
+) -> ContractResult<StakingAprResponse> {
+    if window == 0 {
+        return Err(ContractError::Std(StdError::generic_err("Window must be greater than 0")));
+    }
+
+    let start_height = env.block.height.saturating_sub(window);
+    let (start_height, start_rate) = load_exchange_rate_at(deps.storage, start_height)?
+        .ok_or_else(|| StdError::generic_err("No exchange rate recorded at the window start"))?;
+
+    let end_rate = load_exchange_rate(deps)?;
+
+    let apr = if start_rate.is_zero() || end_rate <= start_rate {
+        Decimal::zero()
+    } else {
+        let growth = end_rate / start_rate - Decimal::one();
+        let elapsed = env.block.height - start_height;
+
+        growth * Decimal::from_ratio(blocks_per_year.unwrap_or(DEFAULT_BLOCKS_PER_YEAR), elapsed)
+    };
+
+    Ok(StakingAprResponse {
+        start_height,
+        end_height: env.block.height,
+        apr,
+    })
+}
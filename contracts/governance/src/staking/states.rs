@@ -1,9 +1,10 @@
-use cosmwasm_std::{Addr, Deps, Order, StdResult, Storage, Uint128};
+use cosmwasm_std::{Addr, Decimal, Deps, DepsMut, Order, StdError, StdResult, Storage, Uint128};
 use cw_storage_plus::{Bound, Item, Map};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use terrapoker::governance::enumerations::PollStatus;
 
+use crate::common::states::{load_contract_staking_token_balance, load_gov_token_total_supply};
 use crate::poll::states::{Poll, VoteInfo};
 
 const STAKING_CONFIG: Item<StakingConfig> = Item::new("staking-config");
@@ -12,6 +13,10 @@ const STAKING_CONFIG: Item<StakingConfig> = Item::new("staking-config");
 pub struct StakingConfig {
     pub distributor: Option<Addr>,
     pub unstake_lock_period: u64,
+    #[serde(default)]
+    pub instant_unstake_fee: Decimal,
+    #[serde(default)]
+    pub instant_unstake_fee_recipient: Option<Addr>, // the fee stays in the pool if not set
 }
 
 impl StakingConfig {
@@ -29,6 +34,10 @@ const STAKING_STATE: Item<StakingState> = Item::new("staking-state");
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct StakingState {
     pub total_unstake_locked: Uint128,
+    #[serde(default)]
+    pub total_voting_reward: Uint128, // funded voting rewards not claimed yet
+    #[serde(default)]
+    pub unallocated_voting_reward: Uint128, // funded voting rewards not allocated to a poll yet
 }
 
 impl StakingState {
@@ -41,15 +50,79 @@ impl StakingState {
     }
 }
 
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+pub const MAX_VOTES_IN_PROGRESS: usize = 50;
+
+// xTPT => TPT exchange rate checkpoints by block height
+const EXCHANGE_RATES: Map<u64, Decimal> = Map::new("exchange-rate");
+
+pub fn save_exchange_rate(
+    storage: &mut dyn Storage,
+    height: u64,
+    staked_amount: Uint128,
+    total_supply: Uint128,
+) -> StdResult<()> {
+    let exchange_rate = if total_supply.is_zero() {
+        Decimal::one()
+    } else {
+        Decimal::from_ratio(staked_amount, total_supply)
+    };
+
+    EXCHANGE_RATES.save(storage, height, &exchange_rate)
+}
+
+// checkpoints the exchange rate of the staking pool and the xTPT supply in storage.
+// `released_amount` staking token and `burned_amount` xTPT are still sent out by messages of the execution.
+pub fn checkpoint_exchange_rate(
+    deps: DepsMut,
+    height: u64,
+    released_amount: Uint128,
+    burned_amount: Uint128,
+) -> StdResult<()> {
+    let staked_amount = load_contract_staking_token_balance(deps.as_ref())?.checked_sub(released_amount)?;
+    let total_supply = load_gov_token_total_supply(deps.as_ref(), None)?.checked_sub(burned_amount)?;
+
+    save_exchange_rate(deps.storage, height, staked_amount, total_supply)
+}
+
+// returns the latest checkpoint at or before the height.
+pub fn load_exchange_rate_at(storage: &dyn Storage, height: u64) -> StdResult<Option<(u64, Decimal)>> {
+    EXCHANGE_RATES
+        .range(storage, None, Some(Bound::inclusive(height)), Order::Descending)
+        .next()
+        .transpose()
+}
+
+pub fn load_exchange_rates(
+    storage: &dyn Storage,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<(u64, Decimal)>> {
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+    let start_after = start_after.map(Bound::exclusive);
+
+    EXCHANGE_RATES
+        .range(storage, start_after, None, Order::Ascending)
+        .take(limit)
+        .collect()
+}
 
 const STAKER_STATES: Map<&Addr, StakerState> = Map::new("staker-state");
 
+// maps (staker, release height) to the unstaked amount
+const UNSTAKE_LOCKED: Map<(&Addr, u64), Uint128> = Map::new("unstake-locked");
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct StakerState {
     pub address: Addr,
     // total staked balance
-    pub votes: Vec<(u64, VoteInfo)>, // maps poll_id to weight voted
-    pub unstake_locked_list: Vec<(u64, Uint128)>,
+    pub votes: Vec<(u64, VoteInfo)>, // maps poll_id to weight voted, up to MAX_VOTES_IN_PROGRESS polls
+    #[serde(default)]
+    pub voting_rewards: Vec<(u64, Uint128)>, // maps poll_id to claimable voting reward
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unstake_locked_list: Vec<(u64, Uint128)>, // unstake requests of older versions until they are migrated
 }
 
 impl StakerState {
@@ -57,6 +130,7 @@ impl StakerState {
         StakerState {
             address: address.clone(),
             votes: vec![],
+            voting_rewards: vec![],
             unstake_locked_list: vec![],
         }
     }
@@ -84,38 +158,132 @@ impl StakerState {
             .collect::<StdResult<Vec<StakerState>>>()
     }
 
+    // removes votes of ended polls and records their voting rewards.
     pub fn clean_votes(&mut self, storage: &dyn Storage) -> () {
-        self.votes.retain(|(poll_id, _)| {
-            Poll::load(storage, &poll_id).ok()
-                .map(|p| p.status == PollStatus::InProgress)
-                .unwrap_or(false)
+        let mut voting_rewards = vec![];
+
+        self.votes.retain(|(poll_id, vote)| {
+            let poll = match Poll::load(storage, poll_id) {
+                Ok(poll) => poll,
+                Err(_) => return false,
+            };
+
+            if poll.status == PollStatus::InProgress {
+                return true;
+            }
+
+            if let Some(reward) = poll.get_voting_reward(vote) {
+                voting_rewards.push((*poll_id, reward));
+            }
+
+            false
         });
+
+        self.voting_rewards.extend(voting_rewards);
+    }
+
+    // returns an error if the staker can not vote on one more poll in progress.
+    // votes of ended polls are cleaned first, so the list stays bounded.
+    pub fn check_vote_limit(&mut self, storage: &dyn Storage, poll_id: u64) -> StdResult<()> {
+        if self.votes.len() < MAX_VOTES_IN_PROGRESS || self.votes.iter().any(|(id, _)| *id == poll_id) {
+            return Ok(());
+        }
+
+        self.clean_votes(storage);
+
+        if self.votes.len() >= MAX_VOTES_IN_PROGRESS {
+            return Err(StdError::generic_err(
+                format!("Can not vote on more than {} polls in progress", MAX_VOTES_IN_PROGRESS),
+            ));
+        }
+
+        Ok(())
+    }
+
+    // removes the voting rewards of the polls and returns them.
+    pub fn take_voting_rewards(&mut self, poll_ids: &[u64]) -> Vec<(u64, Uint128)> {
+        let mut rewards = vec![];
+
+        self.voting_rewards.retain(|(poll_id, reward)| {
+            if poll_ids.contains(poll_id) {
+                rewards.push((*poll_id, *reward));
+                return false;
+            }
+
+            true
+        });
+
+        rewards
     }
 
     // removes not in-progress poll voter info & unlock tokens
     // and returns the largest locked amount in participated polls.
     pub fn get_vote_locked_balance(&self) -> Uint128 {
         self.votes.iter()
-            .map(|(_, v)| v.amount)
+            .map(|(_, v)| v.own_amount())
             .max()
             .unwrap_or_default()
     }
 
-    pub fn clean_unstake_locked_list(&mut self, height: u64) {
-        self.unstake_locked_list.retain(|(h, _a)| {
-            h.clone() >= height
-        });
+    pub fn add_unstake_locked(&self, storage: &mut dyn Storage, height: u64, amount: Uint128) -> StdResult<()> {
+        UNSTAKE_LOCKED.update(storage, (&self.address, height), |locked| -> StdResult<_> {
+            Ok(locked.unwrap_or_default().checked_add(amount)?)
+        })?;
+
+        Ok(())
+    }
+
+    // moves the unstake requests stored inline by older versions to their own map.
+    pub fn migrate_unstake_locked(&mut self, storage: &mut dyn Storage) -> StdResult<()> {
+        for (height, amount) in std::mem::take(&mut self.unstake_locked_list) {
+            self.add_unstake_locked(storage, height, amount)?;
+        }
+
+        Ok(())
+    }
+
+    // removes the unstake request released at the height and returns the amount.
+    pub fn remove_unstake_locked(&self, storage: &mut dyn Storage, height: u64) -> StdResult<Uint128> {
+        let amount = UNSTAKE_LOCKED.may_load(storage, (&self.address, height))?.unwrap_or_default();
+        UNSTAKE_LOCKED.remove(storage, (&self.address, height));
+
+        Ok(amount)
     }
 
-    pub fn get_unstake_claimable_amount(&self, height: u64) -> Uint128 {
+    // removes up to `limit` unstake requests released before the height and returns the amount.
+    pub fn take_unstake_claimable(&self, storage: &mut dyn Storage, height: u64, limit: Option<u32>) -> StdResult<Uint128> {
+        let limit = limit.unwrap_or(MAX_LIMIT).min(MAX_LIMIT) as usize;
+
+        let claimable = UNSTAKE_LOCKED
+            .prefix(&self.address)
+            .range(storage, None, Some(Bound::exclusive(height)), Order::Ascending)
+            .take(limit)
+            .collect::<StdResult<Vec<(u64, Uint128)>>>()?;
+
         let mut amount = Uint128::zero();
-        for (h, a) in self.unstake_locked_list.iter() {
-            if h.clone() < height {
-                amount += a;
-            }
+        for (h, a) in claimable {
+            UNSTAKE_LOCKED.remove(storage, (&self.address, h));
+            amount += a;
         }
 
-        amount
+        Ok(amount)
+    }
+
+    pub fn load_unstake_locked(
+        storage: &dyn Storage,
+        address: &Addr,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    ) -> StdResult<Vec<(u64, Uint128)>> {
+        let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+
+        let start_after = start_after.map(Bound::exclusive);
+
+        UNSTAKE_LOCKED
+            .prefix(address)
+            .range(storage, start_after, None, Order::Ascending)
+            .take(limit)
+            .collect()
     }
 
     // pub fn can_vote(&self, storage: &dyn Storage, contract_available_balance: Uint128, amount: Uint128) -> StdResult<bool> {
@@ -125,6 +293,15 @@ impl StakerState {
     // }
 
     pub fn vote(&mut self, poll_id: u64, vote: VoteInfo) {
-        self.votes.push((poll_id, vote));
+        let prev_vote = self.votes.iter_mut().find(|(id, _)| *id == poll_id);
+
+        match prev_vote {
+            None => self.votes.push((poll_id, vote)),
+            Some((_, prev_vote)) => *prev_vote = vote,
+        }
+    }
+
+    pub fn retract_vote(&mut self, poll_id: u64) {
+        self.votes.retain(|(id, _)| *id != poll_id);
     }
 }
\ No newline at end of file
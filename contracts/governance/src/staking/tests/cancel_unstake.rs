@@ -0,0 +1,65 @@
+use cosmwasm_std::{Addr, Env, MessageInfo, Response, SubMsg, Uint128};
+use cosmwasm_std::testing::mock_info;
+
+use terrapoker::common::ContractResult;
+use terrapoker::governance::execute_msgs::ExecuteMsg;
+use terrapoker::message_factories;
+use terrapoker::mock_querier::{custom_deps, CustomDeps};
+use terrapoker::test_constants::governance::{GOVERNANCE, governance_env};
+use terrapoker::test_utils::expect_generic_err;
+
+use crate::staking::executions::cancel_unstake;
+use crate::staking::states::{StakerState, StakingState};
+use crate::staking::tests::stake_token_hook::STAKER1;
+use crate::tests::init_default;
+
+pub fn exec(deps: &mut CustomDeps, env: Env, info: MessageInfo, height: u64) -> ContractResult<Response> {
+    cancel_unstake(deps.as_mut(), env, info, height)
+}
+
+fn lock_default(deps: &mut CustomDeps) {
+    init_default(deps.as_mut());
+
+    let staker_state = StakerState::default(&Addr::unchecked(STAKER1));
+    staker_state.add_unstake_locked(&mut deps.storage, 100, Uint128::new(10)).unwrap();
+    staker_state.add_unstake_locked(&mut deps.storage, 200, Uint128::new(20)).unwrap();
+    staker_state.save(&mut deps.storage).unwrap();
+
+    let mut staking_state = StakingState::load(&deps.storage).unwrap();
+    staking_state.total_unstake_locked = Uint128::new(30);
+    staking_state.save(&mut deps.storage).unwrap();
+}
+
+#[test]
+fn succeed() {
+    let mut deps = custom_deps();
+
+    lock_default(&mut deps);
+
+    let response = exec(&mut deps, governance_env(), mock_info(STAKER1, &[]), 200).unwrap();
+    assert_eq!(response.messages, vec![
+        SubMsg::new(message_factories::wasm_execute(
+            &Addr::unchecked(GOVERNANCE),
+            &ExecuteMsg::StakeGovernanceTokenHook {
+                staker: STAKER1.to_string(),
+                amount: Uint128::new(20),
+            },
+        )),
+    ]);
+
+    let unstake_locked = StakerState::load_unstake_locked(&deps.storage, &Addr::unchecked(STAKER1), None, None).unwrap();
+    assert_eq!(unstake_locked, vec![(100, Uint128::new(10))]);
+
+    let staking_state = StakingState::load(&deps.storage).unwrap();
+    assert_eq!(staking_state.total_unstake_locked, Uint128::new(10));
+}
+
+#[test]
+fn failed_no_unstake_request() {
+    let mut deps = custom_deps();
+
+    lock_default(&mut deps);
+
+    let result = exec(&mut deps, governance_env(), mock_info(STAKER1, &[]), 300);
+    expect_generic_err(&result, "Unstake request does not exist");
+}
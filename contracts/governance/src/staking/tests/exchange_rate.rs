@@ -0,0 +1,103 @@
+use cosmwasm_std::{Addr, Decimal, Uint128};
+use cosmwasm_std::testing::mock_info;
+
+use terrapoker::mock_querier::custom_deps;
+use terrapoker::test_constants::governance::{GOVERNANCE, governance_env, governance_env_height, GOVERNANCE_TOKEN, STAKING_TOKEN};
+use terrapoker::test_utils::expect_generic_err;
+
+use crate::staking::executions::{receive_staking_reward, stake_token_hook};
+use crate::staking::queries::{get_exchange_rate, get_exchange_rate_history, get_staking_apr};
+use crate::staking::states::save_exchange_rate;
+use crate::staking::tests::stake_token_hook::STAKER1;
+use crate::tests::init_default;
+
+#[test]
+fn succeed_checkpoint_on_stake() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    deps.querier.with_token_balances(&[
+        (STAKING_TOKEN, &[(GOVERNANCE, &Uint128::new(1100))]),
+        (GOVERNANCE_TOKEN, &[(STAKER1, &Uint128::new(500))]),
+    ]);
+
+    let env = governance_env();
+    stake_token_hook(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(GOVERNANCE, &[]),
+        STAKER1.to_string(),
+        Uint128::new(100),
+    ).unwrap();
+
+    let response = get_exchange_rate(deps.as_ref(), env.clone(), Some(env.block.height)).unwrap();
+    assert_eq!(response.exchange_rate, Decimal::percent(200));
+}
+
+#[test]
+fn succeed_checkpoint_on_staking_reward() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    deps.querier.with_token_balances(&[
+        (STAKING_TOKEN, &[(GOVERNANCE, &Uint128::new(1200))]),
+        (GOVERNANCE_TOKEN, &[(STAKER1, &Uint128::new(500))]),
+    ]);
+
+    let env = governance_env();
+    receive_staking_reward(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(STAKING_TOKEN, &[]),
+        Addr::unchecked(GOVERNANCE),
+        Uint128::new(200),
+    ).unwrap();
+
+    let response = get_exchange_rate(deps.as_ref(), env.clone(), Some(env.block.height)).unwrap();
+    assert_eq!(response.exchange_rate, Decimal::percent(240));
+}
+
+#[test]
+fn succeed_history() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    save_exchange_rate(&mut deps.storage, 100, Uint128::new(100), Uint128::new(100)).unwrap();
+    save_exchange_rate(&mut deps.storage, 200, Uint128::new(110), Uint128::new(100)).unwrap();
+    save_exchange_rate(&mut deps.storage, 300, Uint128::new(120), Uint128::new(100)).unwrap();
+
+    let response = get_exchange_rate(deps.as_ref(), governance_env(), Some(250)).unwrap();
+    assert_eq!(response.exchange_rate, Decimal::percent(110));
+
+    let response = get_exchange_rate_history(deps.as_ref(), governance_env(), Some(100), None).unwrap();
+    assert_eq!(
+        response.exchange_rates.iter().map(|r| r.height).collect::<Vec<u64>>(),
+        vec![200, 300],
+    );
+
+    let result = get_exchange_rate(deps.as_ref(), governance_env(), Some(50));
+    expect_generic_err(&result, "No exchange rate recorded at the height");
+}
+
+#[test]
+fn succeed_apr() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    save_exchange_rate(&mut deps.storage, 1000, Uint128::new(100), Uint128::new(100)).unwrap();
+
+    deps.querier.with_token_balances(&[
+        (STAKING_TOKEN, &[(GOVERNANCE, &Uint128::new(110))]),
+        (GOVERNANCE_TOKEN, &[(STAKER1, &Uint128::new(100))]),
+    ]);
+
+    // 10% growth in a tenth of the year
+    let response = get_staking_apr(deps.as_ref(), governance_env_height(2000), 1000, Some(10000)).unwrap();
+    assert_eq!(response.start_height, 1000);
+    assert_eq!(response.end_height, 2000);
+    assert_eq!(response.apr, Decimal::one());
+}
@@ -0,0 +1,115 @@
+use cosmwasm_std::{Addr, CosmosMsg, Decimal, Env, MessageInfo, Response, SubMsg, to_binary, Uint128, WasmMsg};
+use cosmwasm_std::testing::mock_info;
+use cw20::Cw20ExecuteMsg;
+
+use terrapoker::common::ContractResult;
+use terrapoker::message_factories;
+use terrapoker::mock_querier::{custom_deps, CustomDeps};
+use terrapoker::test_constants::governance::{GOVERNANCE, governance_env, GOVERNANCE_TOKEN, STAKING_TOKEN};
+use terrapoker::test_utils::expect_unauthorized_err;
+
+use crate::staking::executions::instant_unstake_token_hook;
+use crate::staking::queries::get_simulate_unstake_amount;
+use crate::staking::states::StakingConfig;
+use crate::staking::tests::stake_token_hook::STAKER1;
+use crate::tests::init_default;
+
+const FEE_RECIPIENT: &str = "terra1fee0recipient0000000000000000000000000";
+
+pub fn exec(deps: &mut CustomDeps, env: Env, info: MessageInfo, staker: String, amount: Uint128) -> ContractResult<Response> {
+    instant_unstake_token_hook(deps.as_mut(), env, info, staker, amount)
+}
+
+fn stake_default(deps: &mut CustomDeps, fee_recipient: Option<Addr>) {
+    init_default(deps.as_mut());
+
+    let mut config = StakingConfig::load(&deps.storage).unwrap();
+    config.instant_unstake_fee = Decimal::percent(10);
+    config.instant_unstake_fee_recipient = fee_recipient;
+    config.save(&mut deps.storage).unwrap();
+
+    deps.querier.with_token_balances(&[
+        (STAKING_TOKEN, &[(GOVERNANCE, &Uint128::new(1000))]),
+        (GOVERNANCE_TOKEN, &[(STAKER1, &Uint128::new(100))]),
+    ]);
+}
+
+#[test]
+fn succeed_fee_stays_in_pool() {
+    let mut deps = custom_deps();
+
+    stake_default(&mut deps, None);
+
+    let response = exec(
+        &mut deps,
+        governance_env(),
+        mock_info(GOVERNANCE, &[]),
+        STAKER1.to_string(),
+        Uint128::new(50),
+    ).unwrap();
+
+    assert_eq!(response.messages, vec![
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: GOVERNANCE_TOKEN.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Burn {
+                amount: Uint128::new(50),
+            }).unwrap(),
+        })),
+        SubMsg::new(message_factories::cw20_transfer(
+            &Addr::unchecked(STAKING_TOKEN),
+            &Addr::unchecked(STAKER1),
+            Uint128::new(450),
+        )),
+    ]);
+}
+
+#[test]
+fn succeed_fee_to_recipient() {
+    let mut deps = custom_deps();
+
+    stake_default(&mut deps, Some(Addr::unchecked(FEE_RECIPIENT)));
+
+    let response = exec(
+        &mut deps,
+        governance_env(),
+        mock_info(GOVERNANCE, &[]),
+        STAKER1.to_string(),
+        Uint128::new(50),
+    ).unwrap();
+
+    assert_eq!(response.messages[2], SubMsg::new(message_factories::cw20_transfer(
+        &Addr::unchecked(STAKING_TOKEN),
+        &Addr::unchecked(FEE_RECIPIENT),
+        Uint128::new(50),
+    )));
+}
+
+#[test]
+fn succeed_simulate() {
+    let mut deps = custom_deps();
+
+    stake_default(&mut deps, None);
+
+    let response = get_simulate_unstake_amount(deps.as_ref(), governance_env(), Uint128::new(50)).unwrap();
+    assert_eq!(response.delayed_amount, Uint128::new(500));
+    assert_eq!(response.instant_amount, Uint128::new(450));
+    assert_eq!(response.instant_fee, Uint128::new(50));
+}
+
+#[test]
+fn failed_invalid_permission() {
+    let mut deps = custom_deps();
+
+    stake_default(&mut deps, None);
+
+    let result = exec(
+        &mut deps,
+        governance_env(),
+        mock_info(STAKER1, &[]),
+        STAKER1.to_string(),
+        Uint128::new(50),
+    );
+
+    expect_unauthorized_err(&result);
+}
@@ -1,12 +1,14 @@
-use cosmwasm_std::{Env, MessageInfo, Response, Uint128};
+use cosmwasm_std::{Decimal, Env, MessageInfo, Response, Uint128};
 
 use terrapoker::common::ContractResult;
 use terrapoker::mock_querier::{custom_deps, CustomDeps};
 use terrapoker::test_constants::default_sender;
 use terrapoker::test_constants::governance::governance_env;
+use terrapoker::test_utils::expect_generic_err;
 
 use crate::staking::executions::instantiate;
-use crate::staking::states::StakingState;
+use crate::staking::states::{StakingConfig, StakingState};
+use crate::tests::STAKING_UNSTAKE_LOCK_PERIOD;
 use terrapoker::governance::execute_msgs::StakingConfigInitMsg;
 
 pub fn exec(
@@ -14,9 +16,13 @@ pub fn exec(
     env: Env,
     info: MessageInfo,
     distributor: Option<String>,
+    instant_unstake_fee: Decimal,
 ) -> ContractResult<Response> {
     let msg = StakingConfigInitMsg {
         distributor,
+        unstake_lock_period: STAKING_UNSTAKE_LOCK_PERIOD,
+        instant_unstake_fee,
+        instant_unstake_fee_recipient: None,
     };
 
     instantiate(deps.as_mut(), env, info, msg)
@@ -31,6 +37,7 @@ pub fn default(deps: &mut CustomDeps) -> (Env, MessageInfo, Response) {
         env.clone(),
         info.clone(),
         None,
+        Decimal::zero(),
     ).unwrap();
 
     (env, info, response)
@@ -43,6 +50,27 @@ fn succeed() {
     default(&mut deps);
 
     // Validate
+    let staking_config = StakingConfig::load(&deps.storage).unwrap();
+    assert_eq!(staking_config.unstake_lock_period, STAKING_UNSTAKE_LOCK_PERIOD);
+    assert_eq!(staking_config.instant_unstake_fee, Decimal::zero());
+
     let staking_state = StakingState::load(&deps.storage).unwrap();
-    assert_eq!(staking_state.total_share, Uint128::zero())
+    assert_eq!(staking_state.total_unstake_locked, Uint128::zero());
+    assert_eq!(staking_state.total_voting_reward, Uint128::zero());
+    assert_eq!(staking_state.unallocated_voting_reward, Uint128::zero());
+}
+
+#[test]
+fn failed_invalid_instant_unstake_fee() {
+    let mut deps = custom_deps();
+
+    let result = exec(
+        &mut deps,
+        governance_env(),
+        default_sender(),
+        None,
+        Decimal::percent(101),
+    );
+
+    expect_generic_err(&result, "instant_unstake_fee must be 0 to 1");
 }
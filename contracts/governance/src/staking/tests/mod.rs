@@ -2,5 +2,9 @@ pub mod instantiate;
 pub mod update_staking_config;
 pub mod stake_token_hook;
 pub mod unstake_token_hook;
+pub mod cancel_unstake;
+pub mod instant_unstake;
+pub mod exchange_rate;
+pub mod unstake_requests;
 
 mod validate;
@@ -1,10 +1,12 @@
-use cosmwasm_std::{Addr, Env, MessageInfo, Response, Uint128};
+use cosmwasm_std::{Addr, attr, CosmosMsg, Env, MessageInfo, Response, SubMsg, to_binary, Uint128, WasmMsg};
 use cosmwasm_std::testing::mock_info;
+use cw20::Cw20ExecuteMsg;
 
 use terrapoker::common::ContractResult;
 use terrapoker::mock_querier::{custom_deps, CustomDeps};
-use terrapoker::test_constants::governance::{GOVERNANCE, governance_env, GOVERNANCE_TOKEN};
+use terrapoker::test_constants::governance::{GOVERNANCE, governance_env, GOVERNANCE_TOKEN, STAKING_TOKEN};
 use terrapoker::test_utils::{expect_generic_err, expect_unauthorized_err};
+use terrapoker::utils::parse_uint128;
 
 use crate::staking::executions::stake_token_hook;
 use crate::staking::states::{StakerState, StakingState};
@@ -18,11 +20,22 @@ pub const STAKER2_STAKE_AMOUNT: Uint128 = Uint128::new(10u128);
 
 pub fn exec(deps: &mut CustomDeps, env: Env, info: MessageInfo, sender: Addr, amount: Uint128) -> ContractResult<Response> {
     deps.querier.plus_token_balances(&[(
-        GOVERNANCE_TOKEN,
+        STAKING_TOKEN,
         &[(GOVERNANCE, &amount)],
     )]);
 
-    stake_token_hook(deps.as_mut(), env, info, sender.to_string(), amount)
+    let response = stake_token_hook(deps.as_mut(), env, info, sender.to_string(), amount)?;
+
+    let mint_amount = response.attributes.iter()
+        .find(|a| a.key == "mint_amount")
+        .map(|a| parse_uint128(&a.value).unwrap())
+        .unwrap_or_default();
+    deps.querier.plus_token_balances(&[(
+        GOVERNANCE_TOKEN,
+        &[(sender.as_str(), &mint_amount)],
+    )]);
+
+    Ok(response)
 }
 
 pub fn will_success(deps: &mut CustomDeps, staker: &str, amount: Uint128) -> (Env, MessageInfo, Response) {
@@ -46,13 +59,32 @@ fn succeed() {
 
     init_default(deps.as_mut());
 
-    will_success(&mut deps, STAKER1, STAKER1_STAKE_AMOUNT);
+    let (_, _, response) = will_success(&mut deps, STAKER1, STAKER1_STAKE_AMOUNT);
+    assert_eq!(response.attributes, vec![
+        attr("action", "stake_token_hook"),
+        attr("sender", STAKER1),
+        attr("amount", STAKER1_STAKE_AMOUNT.to_string()),
+        attr("mint_amount", STAKER1_STAKE_AMOUNT.to_string()),
+    ]);
+
+    // the share of the staker is minted as xTPT
+    assert_eq!(response.messages, vec![
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: GOVERNANCE_TOKEN.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Mint {
+                recipient: STAKER1.to_string(),
+                amount: STAKER1_STAKE_AMOUNT,
+            }).unwrap(),
+        })),
+    ]);
 
     let staking_state = StakingState::load(&deps.storage).unwrap();
-    assert_eq!(staking_state.total_share, STAKER1_STAKE_AMOUNT);
+    assert_eq!(staking_state.total_unstake_locked, Uint128::zero());
+    assert_eq!(staking_state.total_voting_reward, Uint128::zero());
 
-    let staker_state = StakerState::load(&deps.storage, &Addr::unchecked(STAKER1)).unwrap();
-    assert_eq!(staker_state.share, STAKER1_STAKE_AMOUNT);
+    let staker_state = StakerState::load_safe(&deps.storage, &Addr::unchecked(STAKER1)).unwrap();
+    assert_eq!(staker_state, StakerState::default(&Addr::unchecked(STAKER1)));
 }
 
 #[test]
@@ -0,0 +1,120 @@
+use cosmwasm_std::{Addr, SubMsg, to_binary, Uint128};
+use cosmwasm_std::testing::mock_info;
+use cw20::Cw20ExecuteMsg;
+
+use terrapoker::message_factories;
+use terrapoker::mock_querier::{custom_deps, CustomDeps};
+use terrapoker::test_constants::governance::{governance_env, governance_env_height, STAKING_TOKEN};
+use terrapoker::test_utils::expect_generic_err;
+
+use crate::staking::executions::claim_unstaked_token;
+use crate::staking::queries::get_unstake_requests;
+use crate::staking::states::{StakerState, StakingState};
+use crate::staking::tests::stake_token_hook::STAKER1;
+use crate::tests::init_default;
+
+fn lock_default(deps: &mut CustomDeps) {
+    init_default(deps.as_mut());
+
+    let staker_state = StakerState::default(&Addr::unchecked(STAKER1));
+    staker_state.add_unstake_locked(&mut deps.storage, 100, Uint128::new(10)).unwrap();
+    staker_state.add_unstake_locked(&mut deps.storage, 200, Uint128::new(20)).unwrap();
+    staker_state.add_unstake_locked(&mut deps.storage, 200, Uint128::new(5)).unwrap();
+    staker_state.add_unstake_locked(&mut deps.storage, 300, Uint128::new(30)).unwrap();
+    staker_state.save(&mut deps.storage).unwrap();
+
+    let mut staking_state = StakingState::load(&deps.storage).unwrap();
+    staking_state.total_unstake_locked = Uint128::new(65);
+    staking_state.save(&mut deps.storage).unwrap();
+}
+
+#[test]
+fn succeed_query() {
+    let mut deps = custom_deps();
+
+    lock_default(&mut deps);
+
+    let response = get_unstake_requests(deps.as_ref(), governance_env(), STAKER1.to_string(), Some(100), Some(1)).unwrap();
+    assert_eq!(response.requests, vec![(200, Uint128::new(25))]);
+}
+
+#[test]
+fn succeed_claim_with_limit() {
+    let mut deps = custom_deps();
+
+    lock_default(&mut deps);
+
+    claim_unstaked_token(deps.as_mut(), governance_env_height(301), mock_info(STAKER1, &[]), Some(2), None, None).unwrap();
+
+    let unstake_locked = StakerState::load_unstake_locked(&deps.storage, &Addr::unchecked(STAKER1), None, None).unwrap();
+    assert_eq!(unstake_locked, vec![(300, Uint128::new(30))]);
+
+    let staking_state = StakingState::load(&deps.storage).unwrap();
+    assert_eq!(staking_state.total_unstake_locked, Uint128::new(30));
+}
+
+#[test]
+fn succeed_claim_to_recipient() {
+    let mut deps = custom_deps();
+
+    lock_default(&mut deps);
+
+    let vault = "terra1vault000000000000000000000000000000000";
+
+    let response = claim_unstaked_token(
+        deps.as_mut(),
+        governance_env_height(101),
+        mock_info(STAKER1, &[]),
+        None,
+        Some(vault.to_string()),
+        None,
+    ).unwrap();
+
+    assert_eq!(response.messages, vec![
+        SubMsg::new(message_factories::cw20_transfer(
+            &Addr::unchecked(STAKING_TOKEN),
+            &Addr::unchecked(vault),
+            Uint128::new(10),
+        )),
+    ]);
+}
+
+#[test]
+fn succeed_claim_with_send_hook() {
+    let mut deps = custom_deps();
+
+    lock_default(&mut deps);
+
+    let vault = "terra1vault000000000000000000000000000000000";
+    let message = to_binary("deposit").unwrap();
+
+    let response = claim_unstaked_token(
+        deps.as_mut(),
+        governance_env_height(201),
+        mock_info(STAKER1, &[]),
+        None,
+        Some(vault.to_string()),
+        Some(message.clone()),
+    ).unwrap();
+
+    assert_eq!(response.messages, vec![
+        SubMsg::new(message_factories::wasm_execute(
+            &Addr::unchecked(STAKING_TOKEN),
+            &Cw20ExecuteMsg::Send {
+                contract: vault.to_string(),
+                amount: Uint128::new(35),
+                msg: message,
+            },
+        )),
+    ]);
+}
+
+#[test]
+fn failed_nothing_to_claim() {
+    let mut deps = custom_deps();
+
+    lock_default(&mut deps);
+
+    let result = claim_unstaked_token(deps.as_mut(), governance_env_height(100), mock_info(STAKER1, &[]), None, None, None);
+    expect_generic_err(&result, "Nothing to claim");
+}
@@ -0,0 +1,105 @@
+use cosmwasm_std::{Addr, CosmosMsg, Env, MessageInfo, Response, SubMsg, to_binary, Uint128, WasmMsg};
+use cosmwasm_std::testing::mock_info;
+use cw20::Cw20ExecuteMsg;
+
+use terrapoker::common::ContractResult;
+use terrapoker::mock_querier::{custom_deps, CustomDeps};
+use terrapoker::test_constants::default_sender;
+use terrapoker::test_constants::governance::{GOVERNANCE, governance_env, GOVERNANCE_TOKEN, STAKING_TOKEN};
+use terrapoker::test_utils::expect_unauthorized_err;
+use terrapoker::utils::parse_uint128;
+
+use crate::staking::executions::unstake_token_hook;
+use crate::staking::states::{StakerState, StakingState};
+use crate::staking::tests::stake_token_hook::{STAKER1, STAKER1_STAKE_AMOUNT, STAKER2, STAKER2_STAKE_AMOUNT};
+use crate::tests::{init_default, STAKING_UNSTAKE_LOCK_PERIOD};
+
+pub fn exec(deps: &mut CustomDeps, env: Env, info: MessageInfo, staker: String, amount: Uint128) -> ContractResult<Response> {
+    unstake_token_hook(
+        deps.as_mut(),
+        env,
+        info,
+        staker,
+        amount,
+    )
+}
+
+pub fn will_success(deps: &mut CustomDeps, staker: &str, amount: Uint128) -> (Env, MessageInfo, Response) {
+    let env = governance_env();
+    let info = mock_info(GOVERNANCE, &[]);
+
+    let response = exec(
+        deps,
+        env.clone(),
+        info.clone(),
+        staker.to_string(),
+        amount,
+    ).unwrap();
+
+    (env, info, response)
+}
+
+#[test]
+fn succeed() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    let increased_balance = (STAKER1_STAKE_AMOUNT + STAKER2_STAKE_AMOUNT)
+        .checked_mul(Uint128::new(2))
+        .unwrap();
+
+    deps.querier.with_token_balances(&[
+        (STAKING_TOKEN, &[(GOVERNANCE, &increased_balance)]),
+        (GOVERNANCE_TOKEN, &[(STAKER1, &STAKER1_STAKE_AMOUNT), (STAKER2, &STAKER2_STAKE_AMOUNT)]),
+    ]);
+
+    let (env, _, response) = will_success(&mut deps, STAKER1, STAKER1_STAKE_AMOUNT);
+
+    let unstake_amount = response.attributes.iter()
+        .find_map(|v| if v.key == "unstake_amount" {
+            Some(parse_uint128(&v.value).unwrap())
+        } else {
+            None
+        })
+        .unwrap();
+
+    assert_eq!(response.messages, vec![
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: GOVERNANCE_TOKEN.to_string(),
+            funds: vec![],
+            msg: to_binary(&Cw20ExecuteMsg::Burn {
+                amount: STAKER1_STAKE_AMOUNT,
+            }).unwrap(),
+        })),
+    ]);
+
+    let staking_state = StakingState::load(&deps.storage).unwrap();
+    let unstake_locked = StakerState::load_unstake_locked(&deps.storage, &Addr::unchecked(STAKER1), None, None).unwrap();
+
+    assert_eq!(unstake_amount, STAKER1_STAKE_AMOUNT.checked_mul(Uint128::new(2)).unwrap());
+    assert_eq!(staking_state.total_unstake_locked, unstake_amount);
+    assert_eq!(unstake_locked, vec![(env.block.height + STAKING_UNSTAKE_LOCK_PERIOD, unstake_amount)]);
+}
+
+#[test]
+fn remove_completed_vote() {
+    //TODO: Implement after poll test codes.
+}
+
+#[test]
+fn failed_invalid_permission() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    let result = exec(
+        &mut deps,
+        governance_env(),
+        default_sender(),
+        STAKER1.to_string(),
+        STAKER1_STAKE_AMOUNT,
+    );
+
+    expect_unauthorized_err(&result);
+}
@@ -1,11 +1,11 @@
-use cosmwasm_std::{Addr, Env, MessageInfo, Response};
+use cosmwasm_std::{Addr, Decimal, Env, MessageInfo, Response};
 use cosmwasm_std::testing::mock_info;
 
 use terrapoker::common::ContractResult;
 use terrapoker::mock_querier::{custom_deps, CustomDeps};
 use terrapoker::test_constants::default_sender;
 use terrapoker::test_constants::governance::*;
-use terrapoker::test_utils::expect_unauthorized_err;
+use terrapoker::test_utils::{expect_generic_err, expect_unauthorized_err};
 use crate::staking::executions::update_staking_config;
 use crate::staking::states::StakingConfig;
 
@@ -16,18 +16,30 @@ pub fn exec(
     env: Env,
     info: MessageInfo,
     distributor: Option<String>,
+    unstake_lock_period: Option<u64>,
+    instant_unstake_fee: Option<Decimal>,
+    instant_unstake_fee_recipient: Option<String>,
+    clear_instant_unstake_fee_recipient: Option<bool>,
 ) -> ContractResult<Response> {
     update_staking_config(
         deps.as_mut(),
         env,
         info,
         distributor,
+        unstake_lock_period,
+        instant_unstake_fee,
+        instant_unstake_fee_recipient,
+        clear_instant_unstake_fee_recipient,
     )
 }
 
 pub fn will_success(
     deps: &mut CustomDeps,
     distributor: Option<String>,
+    unstake_lock_period: Option<u64>,
+    instant_unstake_fee: Option<Decimal>,
+    instant_unstake_fee_recipient: Option<String>,
+    clear_instant_unstake_fee_recipient: Option<bool>,
 ) -> (Env, MessageInfo, Response) {
     let env = governance_env();
     let info = mock_info(GOVERNANCE, &[]);
@@ -37,6 +49,10 @@ pub fn will_success(
         env.clone(),
         info.clone(),
         distributor,
+        unstake_lock_period,
+        instant_unstake_fee,
+        instant_unstake_fee_recipient,
+        clear_instant_unstake_fee_recipient,
     ).unwrap();
 
     (env, info, response)
@@ -49,15 +65,58 @@ fn succeed() {
     init_default(deps.as_mut());
 
     let distributor = "terra17q4lzg70un58uefr2fwu7uxtgvftspr7d0a6p3";
+    let fee_recipient = "terra1fee0recipient0000000000000000000000000";
 
-    will_success(&mut deps, Some(distributor.to_string()));
+    will_success(
+        &mut deps,
+        Some(distributor.to_string()),
+        Some(200),
+        Some(Decimal::percent(5)),
+        Some(fee_recipient.to_string()),
+        None,
+    );
 
     let config = StakingConfig::load(&deps.storage).unwrap();
     assert_eq!(config, StakingConfig {
         distributor: Some(Addr::unchecked(distributor)),
+        unstake_lock_period: 200,
+        instant_unstake_fee: Decimal::percent(5),
+        instant_unstake_fee_recipient: Some(Addr::unchecked(fee_recipient)),
     });
 }
 
+#[test]
+fn succeed_clear_instant_unstake_fee_recipient() {
+    let mut deps = custom_deps();
+
+    init_default(deps.as_mut());
+
+    let fee_recipient = "terra1fee0recipient0000000000000000000000000";
+
+    will_success(&mut deps, None, None, None, Some(fee_recipient.to_string()), None);
+
+    let config = StakingConfig::load(&deps.storage).unwrap();
+    assert_eq!(config.instant_unstake_fee_recipient, Some(Addr::unchecked(fee_recipient)));
+
+    will_success(&mut deps, None, None, None, None, Some(true));
+
+    let config = StakingConfig::load(&deps.storage).unwrap();
+    assert_eq!(config.instant_unstake_fee_recipient, None);
+
+    let result = exec(
+        &mut deps,
+        governance_env(),
+        mock_info(GOVERNANCE, &[]),
+        None,
+        None,
+        None,
+        Some(fee_recipient.to_string()),
+        Some(true),
+    );
+
+    expect_generic_err(&result, "Can not set and clear instant_unstake_fee_recipient at once");
+}
+
 #[test]
 fn failed_invalid_permission() {
     let mut deps = custom_deps();
@@ -69,7 +128,11 @@ fn failed_invalid_permission() {
         governance_env(),
         default_sender(),
         None,
+        None,
+        None,
+        None,
+        None,
     );
 
     expect_unauthorized_err(&result);
-}
\ No newline at end of file
+}
@@ -1,11 +1,48 @@
-use cosmwasm_std::{Decimal, DepsMut, Env, MessageInfo};
+use cosmwasm_std::{Decimal, DepsMut, Env, MessageInfo, Uint128};
 
 use terrapoker::governance::execute_msgs::{ContractConfigInitMsg, InstantiateMsg, PollConfigInitMsg, StakingConfigInitMsg};
+use terrapoker::governance::models::{ExecutionPolicyMsg, ExecutionTargetPolicyMsg, QuorumBaseMsg};
 use terrapoker::test_constants::{contract_creator};
 use terrapoker::test_constants::governance::*;
 
 use crate::entrypoints;
 
+pub const POLL_DEPOSIT_PERIOD: u64 = 10000;
+pub const POLL_VETO_THRESHOLD_PERCENT: u64 = 33;
+pub const POLL_CANCEL_SLASH_PERCENT: u64 = 10;
+pub const POLL_EXECUTION_EXPIRY_PERIOD: u64 = 100000;
+pub const POLL_EXPEDITED_VOTING_PERIOD: u64 = 100;
+pub const POLL_EXPEDITED_THRESHOLD_PERCENT: u64 = 67;
+pub const POLL_EXPEDITED_PROPOSAL_DEPOSIT: Uint128 = Uint128::new(1_000_000_000_000);
+pub const POLL_EARLY_FINALIZATION: bool = false;
+pub const POLL_MIN_PROPOSER_BALANCE: Uint128 = Uint128::zero();
+pub const POLL_MAX_POLLS_PER_PROPOSER: u64 = 0;
+pub const POLL_PROPOSAL_COOLDOWN_PERIOD: u64 = 0;
+pub const STAKING_UNSTAKE_LOCK_PERIOD: u64 = 100;
+
+pub fn default_quorum_base() -> QuorumBaseMsg {
+    QuorumBaseMsg::TotalSupply {}
+}
+
+pub fn default_execution_policy() -> ExecutionPolicyMsg {
+    ExecutionPolicyMsg {
+        allow_any_target: true,
+        targets: vec![
+            ExecutionTargetPolicyMsg {
+                target: GOVERNANCE_TOKEN.to_string(),
+                denied_messages: vec![
+                    "transfer".to_string(),
+                    "transfer_from".to_string(),
+                    "send".to_string(),
+                    "send_from".to_string(),
+                    "increase_allowance".to_string(),
+                ],
+                threshold: None,
+            },
+        ],
+    }
+}
+
 pub fn init_default(deps: DepsMut) -> (Env, MessageInfo) {
     let env = governance_env();
     let info = contract_creator();
@@ -21,9 +58,27 @@ pub fn init_default(deps: DepsMut) -> (Env, MessageInfo) {
             voting_period: POLL_VOTING_PERIOD,
             execution_delay_period: POLL_EXECUTION_DELAY_PERIOD,
             proposal_deposit: POLL_PROPOSAL_DEPOSIT,
+            deposit_period: POLL_DEPOSIT_PERIOD,
+            veto_threshold: Decimal::percent(POLL_VETO_THRESHOLD_PERCENT),
+            veto_deposit_recipient: None,
+            cancel_slash_rate: Decimal::percent(POLL_CANCEL_SLASH_PERCENT),
+            execution_expiry_period: POLL_EXECUTION_EXPIRY_PERIOD,
+            execution_policy: default_execution_policy(),
+            expedited_voting_period: POLL_EXPEDITED_VOTING_PERIOD,
+            expedited_threshold: Decimal::percent(POLL_EXPEDITED_THRESHOLD_PERCENT),
+            expedited_proposal_deposit: POLL_EXPEDITED_PROPOSAL_DEPOSIT,
+            early_finalization: POLL_EARLY_FINALIZATION,
+            min_proposer_balance: POLL_MIN_PROPOSER_BALANCE,
+            max_polls_per_proposer: POLL_MAX_POLLS_PER_PROPOSER,
+            proposal_cooldown_period: POLL_PROPOSAL_COOLDOWN_PERIOD,
+            quorum_base: default_quorum_base(),
+            guardian: None,
         },
         staking_config: StakingConfigInitMsg {
             distributor: None,
+            unstake_lock_period: STAKING_UNSTAKE_LOCK_PERIOD,
+            instant_unstake_fee: Decimal::zero(),
+            instant_unstake_fee_recipient: None,
         },
     };
 
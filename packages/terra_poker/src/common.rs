@@ -1,6 +1,7 @@
-use cosmwasm_std::{Order, Api, QuerierWrapper, Addr, StdResult, Uint128, Binary};
+use cosmwasm_std::{Order, Api, QuerierWrapper, Addr, StdResult, Uint128, Binary, BankMsg, Coin, CosmosMsg, WasmMsg, from_slice};
 use schemars::JsonSchema;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde::de::{self, IgnoredAny, MapAccess, Visitor};
 
 use super::errors::ContractError;
 use crate::cw20::query_balance;
@@ -70,42 +71,174 @@ impl fmt::Display for Denom {
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
-pub struct ExecutionMsg {
-    pub order: u64,
-    pub contract: String,
-    pub msg: Binary,
+pub enum ExecutionMsg {
+    Execute {
+        order: u64,
+        contract: String,
+        msg: Binary,
+        #[serde(default)]
+        funds: Vec<Coin>,
+    },
+    Migrate {
+        order: u64,
+        contract: String,
+        new_code_id: u64,
+        msg: Binary,
+    },
+    UpdateAdmin {
+        order: u64,
+        contract: String,
+        admin: String,
+    },
+    ClearAdmin {
+        order: u64,
+        contract: String,
+    },
+    BankSend {
+        order: u64,
+        to_address: String,
+        amount: Vec<Coin>,
+    },
+}
+
+impl ExecutionMsg {
+    pub fn order(&self) -> u64 {
+        match self {
+            ExecutionMsg::Execute { order, .. } => *order,
+            ExecutionMsg::Migrate { order, .. } => *order,
+            ExecutionMsg::UpdateAdmin { order, .. } => *order,
+            ExecutionMsg::ClearAdmin { order, .. } => *order,
+            ExecutionMsg::BankSend { order, .. } => *order,
+        }
+    }
 }
 
 impl From<Execution> for ExecutionMsg {
     fn from(e: Execution) -> Self {
-        ExecutionMsg {
-            order: e.order,
-            contract: e.contract.to_string(),
-            msg: e.msg,
-        }
+        ExecutionMsg::from(&e)
     }
 }
 
 impl From<&Execution> for ExecutionMsg {
     fn from(e: &Execution) -> Self {
-        ExecutionMsg {
-            order: e.order,
-            contract: e.contract.to_string(),
-            msg: e.msg.clone(),
+        match e {
+            Execution::Execute { order, contract, msg, funds } => ExecutionMsg::Execute {
+                order: *order,
+                contract: contract.to_string(),
+                msg: msg.clone(),
+                funds: funds.clone(),
+            },
+            Execution::Migrate { order, contract, new_code_id, msg } => ExecutionMsg::Migrate {
+                order: *order,
+                contract: contract.to_string(),
+                new_code_id: *new_code_id,
+                msg: msg.clone(),
+            },
+            Execution::UpdateAdmin { order, contract, admin } => ExecutionMsg::UpdateAdmin {
+                order: *order,
+                contract: contract.to_string(),
+                admin: admin.to_string(),
+            },
+            Execution::ClearAdmin { order, contract } => ExecutionMsg::ClearAdmin {
+                order: *order,
+                contract: contract.to_string(),
+            },
+            Execution::BankSend { order, to_address, amount } => ExecutionMsg::BankSend {
+                order: *order,
+                to_address: to_address.to_string(),
+                amount: amount.clone(),
+            },
         }
     }
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, JsonSchema)]
-pub struct Execution {
-    pub order: u64,
-    pub contract: Addr,
-    pub msg: Binary,
+#[serde(rename_all = "snake_case", from = "StoredExecution")]
+pub enum Execution {
+    Execute {
+        order: u64,
+        contract: Addr,
+        msg: Binary,
+        funds: Vec<Coin>,
+    },
+    Migrate {
+        order: u64,
+        contract: Addr,
+        new_code_id: u64,
+        msg: Binary,
+    },
+    UpdateAdmin {
+        order: u64,
+        contract: Addr,
+        admin: Addr,
+    },
+    ClearAdmin {
+        order: u64,
+        contract: Addr,
+    },
+    BankSend {
+        order: u64,
+        to_address: Addr,
+        amount: Vec<Coin>,
+    },
+}
+
+// Executions stored before the enum was introduced only had a contract and an execute message
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum StoredExecution {
+    Legacy {
+        order: u64,
+        contract: Addr,
+        msg: Binary,
+    },
+    Current(ExecutionMsg),
+}
+
+impl From<StoredExecution> for Execution {
+    fn from(stored: StoredExecution) -> Self {
+        match stored {
+            StoredExecution::Legacy { order, contract, msg } => Execution::Execute {
+                order,
+                contract,
+                msg,
+                funds: vec![],
+            },
+            StoredExecution::Current(msg) => match msg {
+                ExecutionMsg::Execute { order, contract, msg, funds } => Execution::Execute {
+                    order,
+                    contract: Addr::unchecked(contract),
+                    msg,
+                    funds,
+                },
+                ExecutionMsg::Migrate { order, contract, new_code_id, msg } => Execution::Migrate {
+                    order,
+                    contract: Addr::unchecked(contract),
+                    new_code_id,
+                    msg,
+                },
+                ExecutionMsg::UpdateAdmin { order, contract, admin } => Execution::UpdateAdmin {
+                    order,
+                    contract: Addr::unchecked(contract),
+                    admin: Addr::unchecked(admin),
+                },
+                ExecutionMsg::ClearAdmin { order, contract } => Execution::ClearAdmin {
+                    order,
+                    contract: Addr::unchecked(contract),
+                },
+                ExecutionMsg::BankSend { order, to_address, amount } => Execution::BankSend {
+                    order,
+                    to_address: Addr::unchecked(to_address),
+                    amount,
+                },
+            },
+        }
+    }
 }
 
 impl PartialEq for Execution {
     fn eq(&self, other: &Self) -> bool {
-        self.order == other.order
+        self.order() == other.order()
     }
 }
 
@@ -119,16 +252,145 @@ impl PartialOrd for Execution {
 
 impl Ord for Execution {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.order.cmp(&other.order)
+        self.order().cmp(&other.order())
     }
 }
 
 impl Execution {
     pub fn from(api: &dyn Api, msg: &ExecutionMsg) -> StdResult<Execution> {
-        Ok(Execution {
-            order: msg.order,
-            contract: api.addr_validate(&msg.contract)?,
-            msg: msg.msg.clone(),
+        Ok(match msg {
+            ExecutionMsg::Execute { order, contract, msg, funds } => Execution::Execute {
+                order: *order,
+                contract: api.addr_validate(contract)?,
+                msg: msg.clone(),
+                funds: funds.clone(),
+            },
+            ExecutionMsg::Migrate { order, contract, new_code_id, msg } => Execution::Migrate {
+                order: *order,
+                contract: api.addr_validate(contract)?,
+                new_code_id: *new_code_id,
+                msg: msg.clone(),
+            },
+            ExecutionMsg::UpdateAdmin { order, contract, admin } => Execution::UpdateAdmin {
+                order: *order,
+                contract: api.addr_validate(contract)?,
+                admin: api.addr_validate(admin)?,
+            },
+            ExecutionMsg::ClearAdmin { order, contract } => Execution::ClearAdmin {
+                order: *order,
+                contract: api.addr_validate(contract)?,
+            },
+            ExecutionMsg::BankSend { order, to_address, amount } => Execution::BankSend {
+                order: *order,
+                to_address: api.addr_validate(to_address)?,
+                amount: amount.clone(),
+            },
         })
     }
+
+    pub fn order(&self) -> u64 {
+        match self {
+            Execution::Execute { order, .. } => *order,
+            Execution::Migrate { order, .. } => *order,
+            Execution::UpdateAdmin { order, .. } => *order,
+            Execution::ClearAdmin { order, .. } => *order,
+            Execution::BankSend { order, .. } => *order,
+        }
+    }
+
+    // Contract targeted by the execution, or the recipient of a bank send
+    pub fn target(&self) -> &Addr {
+        match self {
+            Execution::Execute { contract, .. } => contract,
+            Execution::Migrate { contract, .. } => contract,
+            Execution::UpdateAdmin { contract, .. } => contract,
+            Execution::ClearAdmin { contract, .. } => contract,
+            Execution::BankSend { to_address, .. } => to_address,
+        }
+    }
+
+    // Variant name of the execute message (e.g. "transfer"), or the execution kind otherwise
+    pub fn message_name(&self) -> StdResult<String> {
+        match self {
+            Execution::Execute { msg, .. } => parse_message_name(msg),
+            Execution::Migrate { .. } => Ok("migrate".to_string()),
+            Execution::UpdateAdmin { .. } => Ok("update_admin".to_string()),
+            Execution::ClearAdmin { .. } => Ok("clear_admin".to_string()),
+            Execution::BankSend { .. } => Ok("bank_send".to_string()),
+        }
+    }
+
+    pub fn to_cosmos_msg(&self) -> CosmosMsg {
+        match self {
+            Execution::Execute { contract, msg, funds, .. } => CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: contract.to_string(),
+                funds: funds.clone(),
+                msg: msg.clone(),
+            }),
+            Execution::Migrate { contract, new_code_id, msg, .. } => CosmosMsg::Wasm(WasmMsg::Migrate {
+                contract_addr: contract.to_string(),
+                new_code_id: *new_code_id,
+                msg: msg.clone(),
+            }),
+            Execution::UpdateAdmin { contract, admin, .. } => CosmosMsg::Wasm(WasmMsg::UpdateAdmin {
+                contract_addr: contract.to_string(),
+                admin: admin.to_string(),
+            }),
+            Execution::ClearAdmin { contract, .. } => CosmosMsg::Wasm(WasmMsg::ClearAdmin {
+                contract_addr: contract.to_string(),
+            }),
+            Execution::BankSend { to_address, amount, .. } => CosmosMsg::Bank(BankMsg::Send {
+                to_address: to_address.to_string(),
+                amount: amount.clone(),
+            }),
+        }
+    }
+}
+
+// Reads the variant name of a json object message ({"transfer": {...}}), which must have exactly one key
+pub fn parse_message_name(msg: &Binary) -> StdResult<String> {
+    from_slice::<MessageName>(msg).map(|name| name.0)
+}
+
+// Top-level key of an execute message, unescaped by the json decoder
+struct MessageName(String);
+
+impl<'de> Deserialize<'de> for MessageName {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_struct("MessageName", &[], MessageNameVisitor)
+    }
+}
+
+struct MessageKey(String);
+
+impl<'de> Deserialize<'de> for MessageKey {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_identifier(MessageNameVisitor).map(|name| MessageKey(name.0))
+    }
+}
+
+struct MessageNameVisitor;
+
+impl<'de> Visitor<'de> for MessageNameVisitor {
+    type Value = MessageName;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+        formatter.write_str("an object with exactly one key")
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+        Ok(MessageName(value.to_string()))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        let name = map.next_key::<MessageKey>()?
+            .ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        map.next_value::<IgnoredAny>()?;
+
+        if map.next_key::<IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(2, &self));
+        }
+
+        Ok(MessageName(name.0))
+    }
 }
@@ -1,7 +1,7 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Binary, Uint128};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
@@ -27,6 +27,7 @@ pub enum ExecuteMsg {
     Transfer {
         recipient: String,
         amount: Uint128,
+        msg: Option<Binary>, // sent to the recipient contract as a cw20 hook if set
     },
 }
 
@@ -0,0 +1,116 @@
+use cosmwasm_std::{to_binary, Binary, Decimal, StdResult, Uint128};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+
+use super::execute_msgs::{ExecuteMsg, UpdatePollConfigMsg};
+use crate::common::ExecutionMsg;
+use crate::community::execute_msgs::ExecuteMsg as CommunityExecuteMsg;
+use crate::distributor::execute_msgs::ExecuteMsg as DistributorExecuteMsg;
+use crate::lp_staking::execute_msgs::ExecuteMsg as LpStakingExecuteMsg;
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum GovAction {
+    UpdatePollConfig(UpdatePollConfigMsg),
+    UpdateStakingConfig {
+        distributor: Option<String>,
+        unstake_lock_period: Option<u64>,
+        instant_unstake_fee: Option<Decimal>,
+        instant_unstake_fee_recipient: Option<String>,
+        clear_instant_unstake_fee_recipient: Option<bool>,
+    },
+    RegisterDistribution {
+        distributor: String,
+        start_height: u64,
+        end_height: u64,
+        recipient: String,
+        amount: Uint128,
+        message: Option<Binary>,
+    },
+    IncreaseAllowance {
+        community: String,
+        address: String,
+        amount: Uint128,
+    },
+    UpdateLpStakingConfig {
+        lp_staking: String,
+        token: Option<String>,
+        pair: Option<String>,
+        lp_token: Option<String>,
+        admin: Option<String>,
+        distribution_schedule: Option<Vec<(u64, u64, Uint128)>>,
+    },
+}
+
+impl GovAction {
+    pub fn to_execution_msg(&self, order: u64, governance: &str) -> StdResult<ExecutionMsg> {
+        let (contract, msg) = match self {
+            GovAction::UpdatePollConfig(msg) => (
+                governance.to_string(),
+                to_binary(&ExecuteMsg::UpdatePollConfig(msg.clone()))?,
+            ),
+            GovAction::UpdateStakingConfig {
+                distributor,
+                unstake_lock_period,
+                instant_unstake_fee,
+                instant_unstake_fee_recipient,
+                clear_instant_unstake_fee_recipient,
+            } => (
+                governance.to_string(),
+                to_binary(&ExecuteMsg::UpdateStakingConfig {
+                    distributor: distributor.clone(),
+                    unstake_lock_period: *unstake_lock_period,
+                    instant_unstake_fee: *instant_unstake_fee,
+                    instant_unstake_fee_recipient: instant_unstake_fee_recipient.clone(),
+                    clear_instant_unstake_fee_recipient: *clear_instant_unstake_fee_recipient,
+                })?,
+            ),
+            GovAction::RegisterDistribution { distributor, start_height, end_height, recipient, amount, message } => (
+                distributor.to_string(),
+                to_binary(&DistributorExecuteMsg::RegisterDistribution {
+                    start_height: *start_height,
+                    end_height: *end_height,
+                    recipient: recipient.to_string(),
+                    amount: *amount,
+                    message: message.clone(),
+                })?,
+            ),
+            GovAction::IncreaseAllowance { community, address, amount } => (
+                community.to_string(),
+                to_binary(&CommunityExecuteMsg::IncreaseAllowance {
+                    address: address.to_string(),
+                    amount: *amount,
+                })?,
+            ),
+            GovAction::UpdateLpStakingConfig { lp_staking, token, pair, lp_token, admin, distribution_schedule } => (
+                lp_staking.to_string(),
+                to_binary(&LpStakingExecuteMsg::UpdateConfig {
+                    token: token.clone(),
+                    pair: pair.clone(),
+                    lp_token: lp_token.clone(),
+                    admin: admin.clone(),
+                    distribution_schedule: distribution_schedule.clone(),
+                })?,
+            ),
+        };
+
+        Ok(ExecutionMsg::Execute {
+            order,
+            contract,
+            msg,
+            funds: vec![],
+        })
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GovActionMsg {
+    pub order: u64,
+    pub action: GovAction,
+}
+
+impl GovActionMsg {
+    pub fn to_execution_msg(&self, governance: &str) -> StdResult<ExecutionMsg> {
+        self.action.to_execution_msg(self.order, governance)
+    }
+}
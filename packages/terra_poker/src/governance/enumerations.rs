@@ -9,6 +9,8 @@ pub enum VoteOption {
     Yes,
     No,
     Abstain,
+    NoWithVeto,
+    Choice(u8), // index of the option in a multiple choice poll
 }
 
 impl fmt::Display for VoteOption {
@@ -17,6 +19,8 @@ impl fmt::Display for VoteOption {
             VoteOption::Yes => write!(f, "yes"),
             VoteOption::No => write!(f, "no"),
             VoteOption::Abstain => write!(f, "abstain"),
+            VoteOption::NoWithVeto => write!(f, "no_with_veto"),
+            VoteOption::Choice(index) => write!(f, "choice_{}", index),
         }
     }
 }
@@ -24,15 +28,52 @@ impl fmt::Display for VoteOption {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum PollStatus {
+    DepositPeriod,
     InProgress,
     Passed,
     Rejected,
     Executed,
+    PartiallyExecuted,
     Failed,
+    Dropped,
+    Cancelled,
+    Expired,
+    Vetoed,
 }
 
 impl fmt::Display for PollStatus {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{:?}", self)
     }
-}
\ No newline at end of file
+}
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum PollKind {
+    Binary {},
+    MultipleChoice {
+        options: Vec<String>,
+        rule: TallyRule,
+    },
+}
+
+impl Default for PollKind {
+    fn default() -> Self {
+        PollKind::Binary {}
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum TallyRule {
+    Plurality, // a voter picks a single option
+    Approval, // a voter approves any number of options with the full voting power
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DepositOutcome {
+    Refunded, // returned to the depositors
+    Confiscated, // sent to the veto deposit recipient or burned
+    Retained, // kept by the contract
+    Pending, // an expedited poll continues as a normal poll
+}
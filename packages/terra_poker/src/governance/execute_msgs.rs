@@ -1,9 +1,11 @@
-use cosmwasm_std::{Decimal, Uint128};
+use cosmwasm_std::{Binary, Decimal, Uint128};
 use cw20::{Cw20ReceiveMsg};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use super::enumerations::VoteOption;
+use super::actions::GovActionMsg;
+use super::enumerations::{PollKind, VoteOption};
+use super::models::{ExecutionPolicyMsg, GuardianMsg, QuorumBaseMsg};
 use crate::common::ExecutionMsg;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -26,12 +28,53 @@ pub struct PollConfigInitMsg {
     pub voting_period: u64,
     pub execution_delay_period: u64,
     pub proposal_deposit: Uint128,
+    pub deposit_period: u64,
+    pub veto_threshold: Decimal,
+    pub veto_deposit_recipient: Option<String>,
+    pub cancel_slash_rate: Decimal,
+    pub execution_expiry_period: u64,
+    pub execution_policy: ExecutionPolicyMsg,
+    pub expedited_voting_period: u64,
+    pub expedited_threshold: Decimal,
+    pub expedited_proposal_deposit: Uint128,
+    pub early_finalization: bool,
+    pub min_proposer_balance: Uint128,
+    pub max_polls_per_proposer: u64,
+    pub proposal_cooldown_period: u64,
+    pub quorum_base: QuorumBaseMsg,
+    pub guardian: Option<GuardianMsg>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct StakingConfigInitMsg {
     pub distributor: Option<String>,
     pub unstake_lock_period: u64,
+    pub instant_unstake_fee: Decimal,
+    pub instant_unstake_fee_recipient: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UpdatePollConfigMsg {
+    pub quorum: Option<Decimal>,
+    pub threshold: Option<Decimal>,
+    pub voting_period: Option<u64>,
+    pub execution_delay_period: Option<u64>,
+    pub proposal_deposit: Option<Uint128>,
+    pub deposit_period: Option<u64>,
+    pub veto_threshold: Option<Decimal>,
+    pub veto_deposit_recipient: Option<String>,
+    pub clear_veto_deposit_recipient: Option<bool>, // burns vetoed deposits again
+    pub cancel_slash_rate: Option<Decimal>,
+    pub execution_expiry_period: Option<u64>,
+    pub execution_policy: Option<ExecutionPolicyMsg>,
+    pub expedited_voting_period: Option<u64>,
+    pub expedited_threshold: Option<Decimal>,
+    pub expedited_proposal_deposit: Option<Uint128>,
+    pub early_finalization: Option<bool>,
+    pub min_proposer_balance: Option<Uint128>,
+    pub max_polls_per_proposer: Option<u64>,
+    pub proposal_cooldown_period: Option<u64>,
+    pub quorum_base: Option<QuorumBaseMsg>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -41,14 +84,11 @@ pub enum ExecuteMsg {
     UpdateStakingConfig {
         distributor: Option<String>,
         unstake_lock_period: Option<u64>,
+        instant_unstake_fee: Option<Decimal>,
+        instant_unstake_fee_recipient: Option<String>,
+        clear_instant_unstake_fee_recipient: Option<bool>, // keeps the fee in the pool again
     },
-    UpdatePollConfig {
-        quorum: Option<Decimal>,
-        threshold: Option<Decimal>,
-        voting_period: Option<u64>,
-        execution_delay_period: Option<u64>,
-        proposal_deposit: Option<Uint128>,
-    },
+    UpdatePollConfig(UpdatePollConfigMsg),
     StakeGovernanceTokenHook {
         staker: String,
         amount: Uint128,
@@ -57,15 +97,41 @@ pub enum ExecuteMsg {
         staker: String,
         amount: Uint128,
     },
-    ClaimUnstakedToken {},
+    InstantUnstakeGovernanceTokenHook {
+        staker: String,
+        amount: Uint128,
+    },
+    ClaimUnstakedToken {
+        limit: Option<u32>, // max number of unstake requests to claim
+        recipient: Option<String>,
+        message: Option<Binary>, // forwards the claimed tokens to the recipient through cw20 send
+    },
+    CancelUnstake { height: u64 }, // release height of the unstake request
     CastVote {
         poll_id: u64,
         vote: VoteOption,
         amount: Uint128,
     },
+    CastSplitVote {
+        poll_id: u64,
+        votes: Vec<(VoteOption, Uint128)>, // splits the voting power across several options
+    },
+    RetractVote { poll_id: u64 },
+    Delegate { delegate: String },
+    Undelegate {},
+    CancelPoll { poll_id: u64 },
     EndPoll { poll_id: u64 },
     ExecutePoll { poll_id: u64 },
+    ExpirePoll { poll_id: u64 },
+    VetoPoll { poll_id: u64 },
+    UpdateGuardian { guardian: Option<GuardianMsg> },
     RunExecution { executions: Vec<ExecutionMsg> },
+    AllocateVotingReward {
+        poll_id: u64,
+        amount: Uint128,
+    },
+    ClaimVotingRewards { poll_ids: Vec<u64> },
+    MigrateLegacyState { limit: Option<u32> }, // continues the migration of states from before the contract version
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -77,8 +143,16 @@ pub enum Cw20HookMsg {
         description: String,
         link: Option<String>,
         executions: Vec<ExecutionMsg>,
+        atomic: Option<bool>,
+        kind: Option<PollKind>,
+        expedited: Option<bool>,
+        actions: Option<Vec<GovActionMsg>>,
     },
     UnstakeGovernanceToken {},
+    InstantUnstake {},
+    Deposit { poll_id: u64 },
+    FundVotingReward { poll_id: Option<u64> }, // unallocated if no poll is given
+    StakingReward {}, // distributor message of staking rewards, checkpoints the exchange rate
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
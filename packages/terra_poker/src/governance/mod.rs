@@ -1,4 +1,5 @@
 pub mod enumerations;
 pub mod execute_msgs;
 pub mod query_msgs;
-pub mod models;
\ No newline at end of file
+pub mod models;
+pub mod actions;
\ No newline at end of file
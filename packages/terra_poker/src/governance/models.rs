@@ -1,4 +1,4 @@
-use cosmwasm_std::Uint128;
+use cosmwasm_std::{Decimal, Uint128};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
@@ -11,4 +11,46 @@ pub struct VoteInfoMsg {
     pub voter: String,
     pub option: VoteOption,
     pub amount: Uint128,
+    pub split: Vec<(VoteOption, Uint128)>,
+    pub delegated_amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DepositInfoMsg {
+    pub depositor: String,
+    pub amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExecutionResultMsg {
+    pub order: u64,
+    pub success: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExecutionPolicyMsg {
+    pub allow_any_target: bool,
+    pub targets: Vec<ExecutionTargetPolicyMsg>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExecutionTargetPolicyMsg {
+    pub target: String,
+    pub denied_messages: Vec<String>,
+    pub threshold: Option<Decimal>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum QuorumBaseMsg {
+    TotalSupply {},
+    ExcludingAddresses { addresses: Vec<String> }, // balances at the start height are excluded
+    Fixed { amount: Uint128 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct GuardianMsg {
+    pub address: String,
+    pub expiry_height: u64,
 }
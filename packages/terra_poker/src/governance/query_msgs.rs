@@ -3,8 +3,9 @@ use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
 use super::super::common::OrderBy;
-use super::enumerations::PollStatus;
-use super::models::VoteInfoMsg;
+use super::actions::GovActionMsg;
+use super::enumerations::{DepositOutcome, PollKind, PollStatus};
+use super::models::{DepositInfoMsg, ExecutionPolicyMsg, ExecutionResultMsg, GuardianMsg, QuorumBaseMsg, VoteInfoMsg};
 use crate::common::ExecutionMsg;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -22,17 +23,45 @@ pub enum QueryMsg {
         limit: Option<u32>,
         order_by: Option<OrderBy>,
     },
+    PollExecutionResults {
+        poll_id: u64,
+    },
+    PollTally {
+        poll_id: u64,
+    },
+    PollDeposits {
+        poll_id: u64,
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
     Voters {
         poll_id: u64,
         start_after: Option<String>,
         limit: Option<u32>,
         order_by: Option<OrderBy>,
     },
+    Delegation {
+        address: String,
+    },
+    DelegatedPower {
+        address: String,
+        height: Option<u64>,
+    },
     StakingConfig {},
     StakingState {},
     StakerState {
         address: String,
     },
+    StakerVotes {
+        address: String,
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    UnstakeRequests {
+        address: String,
+        start_after: Option<u64>, // release height
+        limit: Option<u32>,
+    },
     AllStaker {
         start_after: Option<String>,
         limit: Option<u32>,
@@ -45,7 +74,18 @@ pub enum QueryMsg {
     },
     SimulateUnstakeAmount {
         amount: Uint128,
-    }
+    },
+    ExchangeRate {
+        height: Option<u64>,
+    },
+    ExchangeRateHistory {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    StakingApr {
+        window: u64, // blocks
+        blocks_per_year: Option<u64>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
@@ -57,13 +97,50 @@ pub struct ContractConfigResponse {
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
 pub struct StakingStateResponse {
     pub total_unstake_locked: Uint128,
+    pub total_voting_reward: Uint128,
+    pub unallocated_voting_reward: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulateUnstakeAmountResponse {
+    pub delayed_amount: Uint128,
+    pub instant_amount: Uint128,
+    pub instant_fee: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExchangeRateResponse {
+    pub height: u64,
+    pub exchange_rate: Decimal, // TPT per xTPT
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct ExchangeRateHistoryResponse {
+    pub exchange_rates: Vec<ExchangeRateResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakingAprResponse {
+    pub start_height: u64,
+    pub end_height: u64,
+    pub apr: Decimal,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct StakerStateResponse {
     pub votes: Vec<(u64, VoteInfoMsg)>,
-    pub locked_balance: Uint128,
-    pub unstake_locked_list: Vec<(u64, Uint128)>,
+    pub locked_balance: Uint128, // unstake requests are listed by the UnstakeRequests query
+    pub voting_rewards: Vec<(u64, Uint128)>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StakerVotesResponse {
+    pub votes: Vec<(u64, VoteInfoMsg)>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct UnstakeRequestsResponse {
+    pub requests: Vec<(u64, Uint128)>, // release height, amount
 }
 
 impl Default for StakerStateResponse {
@@ -71,7 +148,7 @@ impl Default for StakerStateResponse {
         StakerStateResponse {
             votes: vec![],
             locked_balance: Uint128::zero(),
-            unstake_locked_list: vec![],
+            voting_rewards: vec![],
         }
     }
 }
@@ -93,6 +170,21 @@ pub struct PollConfigResponse {
     pub voting_period: u64,
     pub execution_delay_period: u64,
     pub proposal_deposit: Uint128,
+    pub deposit_period: u64,
+    pub veto_threshold: Decimal,
+    pub veto_deposit_recipient: Option<String>,
+    pub cancel_slash_rate: Decimal,
+    pub execution_expiry_period: u64,
+    pub execution_policy: ExecutionPolicyMsg,
+    pub expedited_voting_period: u64,
+    pub expedited_threshold: Decimal,
+    pub expedited_proposal_deposit: Uint128,
+    pub early_finalization: bool,
+    pub min_proposer_balance: Uint128,
+    pub max_polls_per_proposer: u64,
+    pub proposal_cooldown_period: u64,
+    pub quorum_base: QuorumBaseMsg,
+    pub guardian: Option<GuardianMsg>,
 }
 
 #[derive(Serialize, Deserialize, Clone, PartialEq, JsonSchema)]
@@ -113,10 +205,21 @@ pub struct PollResponse {
     pub yes_votes: Uint128,
     pub no_votes: Uint128,
     pub abstain_votes: Uint128,
+    pub no_with_veto_votes: Uint128,
+    pub deposit_end_height: u64,
     pub start_height: u64,
     pub end_height: u64,
     pub status: PollStatus,
     pub total_balance_at_start_poll: Uint128,
+    pub atomic: bool,
+    pub kind: PollKind,
+    pub choice_votes: Vec<Uint128>,
+    pub winning_choice: Option<u8>,
+    pub expedited: bool,
+    pub finalized_early: bool,
+    pub voting_reward: Uint128,
+    pub quorum_base: QuorumBaseMsg,
+    pub actions: Vec<GovActionMsg>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -129,6 +232,30 @@ pub struct PollCountResponse {
     pub poll_count: u64,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PollDepositsResponse {
+    pub deposits: Vec<DepositInfoMsg>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PollExecutionResultsResponse {
+    pub results: Vec<ExecutionResultMsg>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PollTallyResponse {
+    pub poll_id: u64,
+    pub quorum: Decimal,
+    pub yes_ratio: Decimal,
+    pub result: String,
+    pub passed: bool,
+    pub blocks_remaining: u64,
+    pub can_end: bool,
+    pub finalized_early: bool,
+    pub expedited_fallback: bool,
+    pub deposit_outcome: DepositOutcome,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct VotersResponse {
     pub voters: Vec<VoteInfoMsg>,
@@ -137,4 +264,14 @@ pub struct VotersResponse {
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct VotingPowerResponse {
     pub voting_power: Decimal,
-}
\ No newline at end of file
+}
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DelegationResponse {
+    pub delegate: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DelegatedPowerResponse {
+    pub delegators: Vec<String>,
+    pub delegated_power: Uint128,
+}